@@ -1,9 +1,9 @@
+use crate::ldf::ldf_error::{tag, take_until, IResult};
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_until, take_while},
+    bytes::complete::{take_while, take_while1},
     multi::many0,
-    sequence::delimited,
-    IResult,
+    sequence::{delimited, preceded},
 };
 
 // Block comment: /* ... */
@@ -11,20 +11,28 @@ fn parse_block_comment(input: &str) -> IResult<&str, &str> {
     delimited(tag("/*"), take_until("*/"), tag("*/"))(input)
 }
 
-// Line comment: // ...
+// Line comment: // ... (up to the end of the line or file)
 fn parse_line_comment(input: &str) -> IResult<&str, &str> {
-    delimited(tag("//"), take_while(|c| c != '\n'), tag("\n"))(input)
+    preceded(tag("//"), take_while(|c| c != '\n'))(input)
 }
 
 /// Skip whitespace and comments (which are treated as whitespace for the parser)
 pub fn skip_whitespace(input: &str) -> IResult<&str, &str> {
-    let (input, _) = _skip_whitespace(input)?;
-    let (input, _) = many0(alt((parse_block_comment, parse_line_comment)))(input).unwrap_or((input, Vec::new()));
-    let (input, _) = _skip_whitespace(input)?;
+    let whitespace = take_while1(|c: char| c.is_whitespace());
+    let (input, _) =
+        many0(alt((whitespace, parse_block_comment, parse_line_comment)))(input).unwrap_or((input, Vec::new()));
     Ok((input, ""))
 }
 
-// Function instead of `let (input, _) = skip_whitespace(input)?;`
-fn _skip_whitespace(input: &str) -> IResult<&str, &str> {
-    take_while(|c: char| c.is_whitespace())(input)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skip_whitespace() {
+        let input = "  /* block */\n// line\n\n  /* block */ // line\nNodes {";
+        assert_eq!(skip_whitespace(input), Ok(("Nodes {", "")));
+        assert_eq!(skip_whitespace("// line at the end of the file"), Ok(("", "")));
+        assert_eq!(skip_whitespace("Nodes"), Ok(("Nodes", "")));
+    }
 }
@@ -0,0 +1,283 @@
+use crate::ldf::ldf_error::LdfError;
+use std::fmt::Write;
+
+/// Severity of a diagnostic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub enum LdfSeverity {
+    /// The file is invalid
+    Error,
+    /// The file can be used, but probably doesn't do what was intended
+    Warning,
+}
+
+impl LdfSeverity {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            LdfSeverity::Error => "error",
+            LdfSeverity::Warning => "warning",
+        }
+    }
+
+    /// ANSI color of the severity (bold red or bold yellow)
+    fn color(self) -> &'static str {
+        match self {
+            LdfSeverity::Error => "\x1b[1;31m",
+            LdfSeverity::Warning => "\x1b[1;33m",
+        }
+    }
+}
+
+/// Problem in a LIN Description File (LDF) that can be rendered compiler-style:
+/// ```text
+/// error: expected `;`
+///   --> line 19, column 5 (Frames section)
+///    |
+/// 19 |     }
+///    |     ^
+///    = hint: expected `;` after frame signal entry
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LdfDiagnostic {
+    pub severity: LdfSeverity,
+
+    /// Identifier of the rule that found the problem, shown after the severity (e.g. `error[REF003]`)
+    pub code: Option<String>,
+
+    /// Description of the problem
+    pub message: String,
+
+    /// Suggestion on how to fix the problem
+    pub hint: Option<String>,
+
+    /// Additional information shown after the hint, may span several lines (e.g. a bit map of a frame)
+    pub note: Option<String>,
+
+    /// Line of the problem, starting at 1
+    pub line: usize,
+
+    /// Column of the problem in characters, starting at 1
+    pub column: usize,
+
+    /// Number of characters to underline, at least 1
+    pub length: usize,
+
+    /// Text of the line with the problem
+    pub snippet: String,
+
+    /// Where the problem is, shown after the line and column (e.g. `Frames section`)
+    pub context: String,
+}
+
+impl LdfDiagnostic {
+    /// Diagnostic without colors
+    pub fn render(&self) -> String {
+        self.render_with(&Style::plain())
+    }
+
+    /// Diagnostic with ANSI colors, for terminals
+    pub fn render_ansi(&self) -> String {
+        self.render_with(&Style::ansi(self.severity))
+    }
+
+    fn render_with(&self, style: &Style) -> String {
+        let line_number = self.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+
+        // Keep tabs in front of the caret, so that it lines up with the snippet
+        let indent: String = self
+            .snippet
+            .chars()
+            .take(self.column.saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let carets = "^".repeat(self.length.max(1));
+
+        let code = match &self.code {
+            Some(code) => format!("[{}]", code),
+            None => String::new(),
+        };
+
+        let mut output = String::new();
+        let _ = writeln!(
+            output,
+            "{}{}{}{}: {}{}{}",
+            style.severity,
+            self.severity.label(),
+            code,
+            style.reset,
+            style.bold,
+            self.message,
+            style.reset
+        );
+        let _ = writeln!(
+            output,
+            "{}{}-->{} line {}, column {} ({})",
+            gutter, style.gutter, style.reset, self.line, self.column, self.context
+        );
+        let _ = writeln!(output, "{} {}|{}", gutter, style.gutter, style.reset);
+        let _ = writeln!(
+            output,
+            "{}{} |{} {}",
+            style.gutter, line_number, style.reset, self.snippet
+        );
+        let _ = write!(
+            output,
+            "{} {}|{} {}{}{}{}",
+            gutter, style.gutter, style.reset, indent, style.severity, carets, style.reset
+        );
+        if let Some(hint) = &self.hint {
+            let _ = write!(
+                output,
+                "\n{} {}={} {}hint{}: {}",
+                gutter, style.gutter, style.reset, style.bold, style.reset, hint
+            );
+        }
+        if let Some(note) = &self.note {
+            let mut lines = note.lines();
+            let _ = write!(
+                output,
+                "\n{} {}={} {}note{}: {}",
+                gutter,
+                style.gutter,
+                style.reset,
+                style.bold,
+                style.reset,
+                lines.next().unwrap_or("")
+            );
+            // Line up the rest of the note with its first line
+            for line in lines {
+                let _ = write!(output, "\n{}         {}", gutter, line);
+            }
+        }
+        output
+    }
+}
+
+impl From<&LdfError> for LdfDiagnostic {
+    fn from(error: &LdfError) -> LdfDiagnostic {
+        LdfDiagnostic {
+            severity: LdfSeverity::Error,
+            code: None,
+            message: error.message.clone(),
+            hint: error.hint(),
+            note: None,
+            line: error.line,
+            column: error.column,
+            length: token_length(&error.snippet, error.column),
+            snippet: error.snippet.clone(),
+            context: format!("{} section", error.section),
+        }
+    }
+}
+
+/// Text of the line of `source` that contains `offset`
+pub(crate) fn snippet_at(source: &str, offset: usize) -> String {
+    let offset = offset.min(source.len());
+    let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[offset..].find('\n').map_or(source.len(), |i| offset + i);
+    source[line_start..line_end].trim_end_matches('\r').to_string()
+}
+
+/// Length of the token at `column` of `snippet`: a whole identifier or number, otherwise a single character
+pub(crate) fn token_length(snippet: &str, column: usize) -> usize {
+    let is_token = |c: &char| c.is_alphanumeric() || *c == '_' || *c == '.';
+    let length = snippet
+        .chars()
+        .skip(column.saturating_sub(1))
+        .take_while(is_token)
+        .count();
+    length.max(1)
+}
+
+/// Escape codes of a rendering, empty for plain text
+struct Style {
+    severity: &'static str,
+    gutter: &'static str,
+    bold: &'static str,
+    reset: &'static str,
+}
+
+impl Style {
+    fn plain() -> Style {
+        Style {
+            severity: "",
+            gutter: "",
+            bold: "",
+            reset: "",
+        }
+    }
+
+    fn ansi(severity: LdfSeverity) -> Style {
+        Style {
+            severity: severity.color(),
+            gutter: "\x1b[1;34m",
+            bold: "\x1b[1m",
+            reset: "\x1b[0m",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_diagnostic() {
+        let diagnostic = LdfDiagnostic {
+            severity: LdfSeverity::Error,
+            code: None,
+            message: "expected `,`".to_string(),
+            hint: Some("the values of a frame signal entry are separated by `,`".to_string()),
+            note: None,
+            line: 9,
+            column: 17,
+            length: token_length("        Signal1 0 ;", 17),
+            snippet: "        Signal1 0 ;".to_string(),
+            context: "Frames section".to_string(),
+        };
+
+        let expected = [
+            "error: expected `,`",
+            " --> line 9, column 17 (Frames section)",
+            "  |",
+            "9 |         Signal1 0 ;",
+            "  |                 ^",
+            "  = hint: the values of a frame signal entry are separated by `,`",
+        ];
+        assert_eq!(diagnostic.render(), expected.join("\n"));
+
+        let ansi = diagnostic.render_ansi();
+        assert!(ansi.starts_with("\x1b[1;31merror\x1b[0m: \x1b[1mexpected `,`\x1b[0m\n"));
+        assert!(ansi.contains("\x1b[1;31m^\x1b[0m"));
+    }
+
+    #[test]
+    fn test_render_diagnostic_tabs_and_tokens() {
+        let diagnostic = LdfDiagnostic {
+            severity: LdfSeverity::Warning,
+            code: Some("REF003".to_string()),
+            message: "unknown signal".to_string(),
+            hint: None,
+            note: Some("signals are defined in\nthe Signals section".to_string()),
+            line: 12,
+            column: 3,
+            length: token_length("\t\tSignal1, 0 ;", 3),
+            snippet: "\t\tSignal1, 0 ;".to_string(),
+            context: "Frames section".to_string(),
+        };
+
+        let expected = [
+            "warning[REF003]: unknown signal",
+            "  --> line 12, column 3 (Frames section)",
+            "   |",
+            "12 | \t\tSignal1, 0 ;",
+            "   | \t\t^^^^^^^",
+            "   = note: signals are defined in",
+            "           the Signals section",
+        ];
+        assert_eq!(diagnostic.render(), expected.join("\n"));
+    }
+}
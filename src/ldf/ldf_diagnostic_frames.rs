@@ -1,8 +1,8 @@
 use crate::ldf::ldf_comment::skip_whitespace;
-use nom::{
-    bytes::complete::{tag, take_while},
-    IResult,
-};
+use crate::ldf::ldf_error::{tag, IResult};
+use crate::ldf::ldf_number::parse_integer;
+use crate::ldf::ldf_span::{LdfNamedSpan, LdfSpan, LineIndex, ResolveSpans};
+use nom::bytes::complete::take_while;
 
 /// `Diagnostic_frames` section of a LIN Description File (LDF)
 /// ```text
@@ -40,6 +40,11 @@ pub struct LdfDiagnosticFrame {
 
     /// Frame signals
     pub signals: Vec<LdfDiagnosticFrameSignal>,
+
+    /// Location of the frame in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
 }
 
 /// One signal section of a Frame in a LIN Description File (LDF).
@@ -55,6 +60,30 @@ pub struct LdfDiagnosticFrameSignal {
 
     /// Signal start bit
     pub start_bit: u8,
+
+    /// Location of the signal entry in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
+
+    /// Identifiers referenced by the signal entry (signal), with their location in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub references: Vec<LdfNamedSpan>,
+}
+
+impl ResolveSpans for LdfDiagnosticFrame {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+        self.signals.resolve_spans(index);
+    }
+}
+
+impl ResolveSpans for LdfDiagnosticFrameSignal {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+        self.references.resolve_spans(index);
+    }
 }
 
 /*
@@ -107,12 +136,11 @@ pub fn parse_ldf_diagnostic_frames(s: &str) -> IResult<&str, Vec<LdfDiagnosticFr
         // - May be any number of spaces before and after the colon
         // - May be any number of spaces before and after the frame ID
         // - May be any number of spaces before and after the opening curly brace
+        let entry = remaining;
         let (s, frame_name) = take_while(|c: char| c != ':' && c != '{')(remaining)?;
         let (s, _) = tag(":")(s)?;
         let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("0x")(s)?;
-        let (s, frame_id) = take_while(|c: char| c != ' ' && c != '{')(s)?;
-        let frame_id = u8::from_str_radix(frame_id, 16).unwrap();
+        let (s, frame_id) = parse_integer(s)?;
         let (s, _) = skip_whitespace(s)?;
         let (s, _) = tag("{")(s)?;
         let (s, _) = skip_whitespace(s)?;
@@ -126,29 +154,33 @@ pub fn parse_ldf_diagnostic_frames(s: &str) -> IResult<&str, Vec<LdfDiagnosticFr
             // - May be any number of spaces before and after the comma
             // - May be any number of spaces before and after the start bit
             // - May be any number of spaces before and after the semicolon
+            let signal_entry = remaining;
             let (s, signal_name) = take_while(|c: char| c != ',' && c != ';')(remaining)?;
             let (s, _) = tag(",")(s)?;
             let (s, _) = skip_whitespace(s)?;
-            let (s, start_bit) = take_while(|c: char| c.is_numeric())(s)?;
-            let start_bit = u8::from_str_radix(start_bit, 10).unwrap();
+            let (s, start_bit) = parse_integer(s)?;
             let (s, _) = skip_whitespace(s)?;
             let (s, _) = tag(";")(s)?;
 
             signals.push(LdfDiagnosticFrameSignal {
                 signal_name: signal_name.to_string(),
                 start_bit,
+                span: LdfSpan::new(signal_entry, s),
+                references: vec![LdfNamedSpan::of(signal_entry, signal_name.trim())],
             });
             let (s, _) = skip_whitespace(s)?;
             remaining = s;
         }
 
         let (s, _) = tag("}")(remaining)?;
+        let span = LdfSpan::new(entry, s);
         let (s, _) = skip_whitespace(s)?;
 
         diagnostic_frames.push(LdfDiagnosticFrame {
             frame_name: frame_name.to_string(),
             frame_id,
             signals,
+            span,
         });
 
         remaining = s;
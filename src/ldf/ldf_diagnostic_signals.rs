@@ -1,8 +1,8 @@
 use crate::ldf::ldf_comment::skip_whitespace;
-use nom::{
-    bytes::complete::{tag, take_while},
-    IResult,
-};
+use crate::ldf::ldf_error::{tag, IResult};
+use crate::ldf::ldf_number::parse_integer;
+use crate::ldf::ldf_span::{LdfSpan, LineIndex, ResolveSpans};
+use nom::bytes::complete::take_while;
 
 /// `Diagnostic_signals` section of a LIN Description File (LDF) for LIN 2.1
 /// ```text
@@ -25,7 +25,7 @@ use nom::{
 ///  SlaveRespB7: 8, 0 ;
 /// }
 /// ```
-
+///
 /// Diagnostic signal in the `Diagnostic_signals` section of a LIN Description File (LDF) for LIN 2.1
 /// ```text
 /// MasterReqB0: 8, 0 ;
@@ -42,6 +42,17 @@ pub struct LdfDiagnosticSignal {
 
     /// Initial value of the signal.
     pub init_value: u8,
+
+    /// Location of the signal in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
+}
+
+impl ResolveSpans for LdfDiagnosticSignal {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+    }
 }
 
 pub fn parse_ldf_diagnostic_signals(s: &str) -> IResult<&str, Vec<LdfDiagnosticSignal>> {
@@ -72,23 +83,26 @@ pub fn parse_ldf_diagnostic_signals(s: &str) -> IResult<&str, Vec<LdfDiagnosticS
         // - May be any number of spaces before and after the init value
         // - May be any number of spaces before and after the semicolon
         let (s, _) = skip_whitespace(remaining)?;
+        let entry = s;
         let (s, signal_name) = take_while(|c: char| c.is_alphanumeric() || c == '_')(s)?;
         let (s, _) = skip_whitespace(s)?;
         let (s, _) = tag(":")(s)?;
         let (s, _) = skip_whitespace(s)?;
-        let (s, signal_size) = take_while(|c: char| c.is_numeric())(s)?;
+        let (s, signal_size) = parse_integer(s)?;
         let (s, _) = skip_whitespace(s)?;
         let (s, _) = tag(",")(s)?;
         let (s, _) = skip_whitespace(s)?;
-        let (s, init_value) = take_while(|c: char| c.is_numeric())(s)?;
+        let (s, init_value) = parse_integer(s)?;
         let (s, _) = skip_whitespace(s)?;
         let (s, _) = tag(";")(s)?;
+        let span = LdfSpan::new(entry, s);
         let (s, _) = skip_whitespace(s)?;
 
         diagnostic_signals.push(LdfDiagnosticSignal {
             name: signal_name.to_string(),
-            length: signal_size.parse().unwrap(),
-            init_value: init_value.parse().unwrap(),
+            length: signal_size,
+            init_value,
+            span,
         });
 
         remaining = s;
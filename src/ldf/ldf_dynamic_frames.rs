@@ -0,0 +1,74 @@
+use crate::ldf::ldf_comment::skip_whitespace;
+use crate::ldf::ldf_error::{tag, take_until, IResult};
+use crate::ldf::ldf_number::parse_integer_token;
+
+/*
+Dynamic_frames {
+  0x38, 0x39, 58 ;
+}
+*/
+
+/// `Dynamic_frames` section of a LIN 1.3 Description File (LDF)
+///
+/// Lists the frame identifiers that are reserved for dynamic frames. It only exists in LIN 1.3.
+/// ```text
+/// Dynamic_frames {
+///   0x38, 0x39, 58 ;
+/// }
+/// ```
+pub fn parse_ldf_dynamic_frames(s: &str) -> IResult<&str, Vec<u8>> {
+    // `Dynamic_frames {` or `Dynamic_frames{` or ...
+    // - May be any number of spaces before and after the "Dynamic_frames" tag
+    // - May be any number of spaces before and after the opening curly brace
+    let (s, _) = skip_whitespace(s)?;
+
+    // Assume that if the section label is not present, then the section is not present.
+    if !s.starts_with("Dynamic_frames") {
+        return Ok((s, Vec::new()));
+    }
+
+    let (s, _) = tag("Dynamic_frames")(s)?;
+    let (s, _) = skip_whitespace(s)?;
+    let (s, _) = tag("{")(s)?;
+    let (s, _) = skip_whitespace(s)?;
+
+    let mut dynamic_frames = Vec::new();
+    let mut remaining = s;
+
+    while !remaining.starts_with('}') {
+        // `0x38, 0x39 ;` or `0x38,0x39;` or ...
+        // - May be any number of spaces before and after each frame ID
+        // - May be any number of spaces before and after the semicolon
+        let (s, frame_ids) = take_until(";")(remaining)?;
+        let (s, _) = tag(";")(s)?;
+        let (s, _) = skip_whitespace(s)?;
+
+        for frame_id in frame_ids.split(',') {
+            let (_, frame_id) = parse_integer_token(frame_id)?;
+            dynamic_frames.push(frame_id);
+        }
+
+        remaining = s;
+    }
+
+    let (remaining, _) = tag("}")(remaining)?;
+
+    Ok((remaining, dynamic_frames))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ldf_dynamic_frames() {
+        let input = r#"
+            Dynamic_frames {
+                0x38, 0x39 , 58 ;
+            }
+        "#;
+
+        let (_, dynamic_frames) = parse_ldf_dynamic_frames(input).unwrap();
+        assert_eq!(dynamic_frames, vec![0x38, 0x39, 58]);
+    }
+}
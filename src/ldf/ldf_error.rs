@@ -0,0 +1,324 @@
+use crate::ldf::ldf_diagnostic::{snippet_at, LdfDiagnostic};
+use nom::error::{ErrorKind, ParseError};
+use std::fmt;
+
+/// Error of a LIN Description File (LDF) that could not be parsed.
+///
+/// Points at the location in the file where parsing stopped. It is displayed compiler-style
+/// (see [`LdfDiagnostic`]):
+/// ```text
+/// error: expected `;`
+///   --> line 19, column 5 (Frames section)
+///    |
+/// 19 |     }
+///    |     ^
+///    = hint: expected `;` after frame signal entry
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub struct LdfError {
+    /// Line of the error, starting at 1
+    pub line: usize,
+
+    /// Column of the error in characters, starting at 1
+    pub column: usize,
+
+    /// Byte offset of the error from the start of the file
+    pub offset: usize,
+
+    /// Section that was being parsed, e.g. `Frames` (or `header` for the file header)
+    pub section: String,
+
+    /// Token that was expected at the error location, e.g. `;`
+    pub expected: Option<String>,
+
+    /// Text of the line with the error
+    pub snippet: String,
+
+    /// Description of the error
+    pub message: String,
+
+    /// Parser that failed, `None` for missing and duplicate sections
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "ts-rs", ts(skip))]
+    pub(crate) kind: Option<ErrorKind>,
+}
+
+impl LdfError {
+    /// Error at `offset` bytes into `source`
+    pub(crate) fn new(source: &str, offset: usize, section: &str, expected: Option<&str>, message: String) -> LdfError {
+        let offset = offset.min(source.len());
+        let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+
+        LdfError {
+            line: source[..offset].matches('\n').count() + 1,
+            column: source[line_start..offset].chars().count() + 1,
+            offset,
+            section: section.to_string(),
+            expected: expected.map(|expected| expected.to_string()),
+            snippet: snippet_at(source, offset),
+            message,
+            kind: None,
+        }
+    }
+
+    /// Error returned by one of the section parsers while parsing `source`
+    pub(crate) fn from_parse_error(source: &str, section: &str, error: nom::Err<LdfParseError<&str>>) -> LdfError {
+        match error {
+            nom::Err::Error(error) | nom::Err::Failure(error) => {
+                // The error input is a slice of the source (not always a suffix, e.g. for a single list item)
+                let offset = (error.input.as_ptr() as usize)
+                    .checked_sub(source.as_ptr() as usize)
+                    .filter(|offset| *offset <= source.len())
+                    .unwrap_or(source.len());
+                let message = match error.expected {
+                    Some(expected) => format!("expected `{}`", expected),
+                    None => describe_error_kind(error.kind),
+                };
+                LdfError {
+                    kind: Some(error.kind),
+                    ..LdfError::new(source, offset, section, error.expected, message)
+                }
+            }
+            nom::Err::Incomplete(_) => LdfError {
+                kind: Some(ErrorKind::Complete),
+                ..LdfError::new(
+                    source,
+                    source.len(),
+                    section,
+                    None,
+                    "unexpected end of file".to_string(),
+                )
+            },
+        }
+    }
+}
+
+impl LdfError {
+    /// Suggestion on how to fix the error, e.g. "expected `;` after signal definition"
+    pub fn hint(&self) -> Option<String> {
+        match (self.kind, self.expected.as_deref()) {
+            // Missing section
+            (_, Some(expected)) if expected == self.section => Some(format!("add a `{} {{ ... }}` section", expected)),
+            (_, Some(expected)) => expected_hint(&self.section, expected),
+            // Duplicate section
+            (None, None) => Some(format!("move the entries into the first {} section", self.section)),
+            (Some(ErrorKind::Permutation), None) => Some("each attribute of a node may only be given once".to_string()),
+            (Some(ErrorKind::Digit), None) => {
+                Some("integers are decimal (`255`) or hexadecimal (`0xFF`) and must fit the field".to_string())
+            }
+            // The header has no times, only the speed is range checked
+            (Some(ErrorKind::MapRes), None) if self.section == "header" => {
+                Some("`LIN_speed` must be above 0 and at most 65.535 kbps".to_string())
+            }
+            (Some(ErrorKind::MapRes), None) => Some("times can't be negative, e.g. `10 ms`".to_string()),
+            (Some(ErrorKind::Verify), None) if self.section == "header" => {
+                Some("versions are written like `2.1`, `J2602_1_1.0` or `ISO17987:2015`".to_string())
+            }
+            (Some(ErrorKind::Float), None) => {
+                Some("numbers may have a sign, a fraction and an exponent, e.g. `-1.5E-3`".to_string())
+            }
+            (Some(_), None) => None,
+        }
+    }
+
+    /// Error rendered with ANSI colors, for terminals. [`fmt::Display`] renders it without colors.
+    pub fn render_ansi(&self) -> String {
+        LdfDiagnostic::from(self).render_ansi()
+    }
+}
+
+impl fmt::Display for LdfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", LdfDiagnostic::from(self).render())
+    }
+}
+
+impl std::error::Error for LdfError {}
+
+fn describe_error_kind(kind: ErrorKind) -> String {
+    match kind {
+        ErrorKind::Digit => "invalid or out of range integer".to_string(),
+        ErrorKind::Float => "invalid number".to_string(),
+        ErrorKind::Tag => "unexpected token".to_string(),
+        ErrorKind::TakeWhile1 => "expected an identifier".to_string(),
+        ErrorKind::TooLarge => "too many values".to_string(),
+        ErrorKind::Eof => "unexpected characters after value".to_string(),
+        ErrorKind::Verify => "invalid or incomplete definition".to_string(),
+        ErrorKind::Permutation => "duplicate attribute".to_string(),
+        ErrorKind::MapRes => "value out of range".to_string(),
+        kind => format!("unexpected input ({})", kind.description()),
+    }
+}
+
+/// Hint for a missing token, e.g. "expected `;` after signal definition"
+fn expected_hint(section: &str, expected: &str) -> Option<String> {
+    let entry = match section {
+        "header" => "statement",
+        "Nodes" => "node definition",
+        "Node_composition" => "composite node",
+        "Signals" => "signal definition",
+        "Diagnostic_signals" => "diagnostic signal definition",
+        "Frames" => "frame signal entry",
+        "Sporadic_frames" => "sporadic frame definition",
+        "Dynamic_frames" => "dynamic frame identifier",
+        "Event_triggered_frames" => "event triggered frame definition",
+        "Diagnostic_frames" => "diagnostic frame signal entry",
+        "Node_attributes" => "node attribute",
+        "Schedule_tables" => "schedule table entry",
+        "Signal_groups" => "signal group entry",
+        "Signal_encoding_types" => "encoding value",
+        "Signal_representation" => "signal representation",
+        _ => "entry",
+    };
+    let hint = match expected {
+        ";" => format!("expected `;` after {}", entry),
+        "," => format!("the values of a {} are separated by `,`", entry),
+        ":" => format!("a {} starts with its name followed by `:`", entry),
+        "=" => "statements and attributes are written as `name = value ;`".to_string(),
+        "{" => "expected `{` to open the block".to_string(),
+        "}" => "every `{` needs a matching `}`".to_string(),
+        "\"" => "versions and texts are written in double quotes, e.g. `\"2.1\"`".to_string(),
+        "ms" => "times are given in milliseconds, e.g. `10 ms`".to_string(),
+        "kbps" => "the speed is given in kbit/s, e.g. `19.2 kbps`".to_string(),
+        "LIN_protocol" | "configured_NAD" | "product_id" => {
+            "every node needs the `LIN_protocol`, `configured_NAD` and `product_id` attributes".to_string()
+        }
+        _ => return None,
+    };
+    Some(hint)
+}
+
+/// Error of the section parsers. Unlike [`nom::error::Error`] it remembers the expected token.
+#[derive(Debug, PartialEq)]
+pub struct LdfParseError<I> {
+    /// Remaining input at the error location
+    pub input: I,
+
+    /// Parser that failed
+    pub kind: ErrorKind,
+
+    /// Token that was expected, if the failing parser was looking for one
+    pub expected: Option<&'static str>,
+}
+
+impl<I> LdfParseError<I> {
+    pub fn new(input: I, kind: ErrorKind) -> LdfParseError<I> {
+        LdfParseError {
+            input,
+            kind,
+            expected: None,
+        }
+    }
+}
+
+impl<I> ParseError<I> for LdfParseError<I> {
+    fn from_error_kind(input: I, kind: ErrorKind) -> Self {
+        LdfParseError::new(input, kind)
+    }
+
+    fn append(_: I, _: ErrorKind, other: Self) -> Self {
+        // Keep the innermost error, it is the most precise one
+        other
+    }
+}
+
+/// [`nom::IResult`] with [`LdfParseError`] as error type
+pub type IResult<I, O> = nom::IResult<I, O, LdfParseError<I>>;
+
+/// Same as [`nom::bytes::complete::tag`], but remembers the expected token on failure
+pub fn tag<'a>(expected: &'static str) -> impl Fn(&'a str) -> IResult<&'a str, &'a str> {
+    move |s: &'a str| match s.starts_with(expected) {
+        true => Ok((&s[expected.len()..], &s[..expected.len()])),
+        false => Err(nom::Err::Error(LdfParseError {
+            input: s,
+            kind: ErrorKind::Tag,
+            expected: Some(expected),
+        })),
+    }
+}
+
+/// Same as [`nom::bytes::complete::take_until`], but remembers the expected token on failure
+pub fn take_until<'a>(expected: &'static str) -> impl Fn(&'a str) -> IResult<&'a str, &'a str> {
+    move |s: &'a str| match s.find(expected) {
+        Some(i) => Ok((&s[i..], &s[..i])),
+        None => Err(nom::Err::Error(LdfParseError {
+            input: s,
+            kind: ErrorKind::TakeUntil,
+            expected: Some(expected),
+        })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ldf_error_location() {
+        let source = "Frames {\n  Frame1: 0x10, Master, 8 {\r\n    Signal1 0 ;\n  }\n}";
+        let remaining = &source[source.find("0 ;").unwrap()..];
+        let error = LdfError::from_parse_error(source, "Frames", tag(",")(remaining).unwrap_err());
+
+        assert_eq!(error.line, 3);
+        assert_eq!(error.column, 13);
+        assert_eq!(error.offset, source.find("0 ;").unwrap());
+        assert_eq!(error.section, "Frames");
+        assert_eq!(error.expected, Some(",".to_string()));
+        assert_eq!(error.snippet, "    Signal1 0 ;");
+        assert_eq!(
+            error.hint().as_deref(),
+            Some("the values of a frame signal entry are separated by `,`")
+        );
+
+        let expected = [
+            "error: expected `,`",
+            " --> line 3, column 13 (Frames section)",
+            "  |",
+            "3 |     Signal1 0 ;",
+            "  |             ^",
+            "  = hint: the values of a frame signal entry are separated by `,`",
+        ];
+        assert_eq!(error.to_string(), expected.join("\n"));
+        assert!(error.render_ansi().contains("\x1b[1;31merror\x1b[0m"));
+    }
+
+    #[test]
+    fn test_ldf_error_hint() {
+        let source = "LIN_speed = 0 kbps ;\nMaster: Master, -5 ms, 0.1 ms ;";
+        let error = |section: &str, kind: ErrorKind| {
+            let error = LdfParseError::new(&source[source.find('-').unwrap()..], kind);
+            LdfError::from_parse_error(source, section, nom::Err::Error(error))
+        };
+
+        // The hint depends on the failed parser and the section, not on the message
+        assert_eq!(
+            error("header", ErrorKind::MapRes).hint().as_deref(),
+            Some("`LIN_speed` must be above 0 and at most 65.535 kbps")
+        );
+        assert_eq!(
+            error("Nodes", ErrorKind::MapRes).hint().as_deref(),
+            Some("times can't be negative, e.g. `10 ms`")
+        );
+        assert_eq!(error("Nodes", ErrorKind::Tag).hint(), None);
+
+        let duplicate = LdfError::new(source, 0, "Nodes", None, "duplicate Nodes section".to_string());
+        assert_eq!(
+            duplicate.hint().as_deref(),
+            Some("move the entries into the first Nodes section")
+        );
+        let missing = LdfError::new(source, 0, "Nodes", Some("Nodes"), "missing Nodes section".to_string());
+        assert_eq!(missing.hint().as_deref(), Some("add a `Nodes { ... }` section"));
+    }
+
+    #[test]
+    fn test_ldf_error_end_of_file() {
+        let source = "Nodes {\n";
+        let error = LdfError::new(source, source.len(), "Nodes", Some("}"), "expected `}`".to_string());
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 1);
+        assert_eq!(error.snippet, "");
+    }
+}
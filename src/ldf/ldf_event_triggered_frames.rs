@@ -0,0 +1,223 @@
+use crate::ldf::ldf_comment::skip_whitespace;
+use crate::ldf::ldf_error::{tag, IResult};
+use crate::ldf::ldf_identifier::{parse_identifier, parse_identifier_list};
+use crate::ldf::ldf_number::parse_integer;
+use crate::ldf::ldf_span::{LdfNamedSpan, LdfSpan, LineIndex, ResolveSpans};
+
+/// `Event_triggered_frames` section of a LIN Description File (LDF)
+/// ```text
+/// Event_triggered_frames {
+///   ETF_Doors: Collision_Doors, 0x3A, DoorLeft, DoorRight ;
+///   ETF_Switches: 0x3B, SwitchLeft, SwitchRight ;
+/// }
+/// ```
+/// ---
+/// Event triggered frame in the `Event_triggered_frames` section of a LIN Description File (LDF)
+/// ```text
+/// ETF_Doors: Collision_Doors, 0x3A, DoorLeft, DoorRight ;
+/// ```
+/// Reads as:
+/// ```text
+/// <event_trig_frm_name>: [<collision_resolving_schedule_table>,] <frame_id>, <frame_name> [, <frame_name>] ;
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub struct LdfEventTriggeredFrame {
+    /// Event triggered frame name
+    pub frame_name: String,
+
+    /// Frame ID
+    pub frame_id: u8,
+
+    /// Schedule table the master switches to in order to resolve collisions.
+    /// It was added in LIN 2.1 version, so it may be left out in older files.
+    pub collision_resolving_schedule_table: Option<String>,
+
+    /// Unconditional frames associated with the event triggered frame
+    pub associated_frames: Vec<String>,
+
+    /// Location of the event triggered frame in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
+
+    /// Identifiers referenced by the event triggered frame (collision resolving schedule table, associated frames), with their location in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub references: Vec<LdfNamedSpan>,
+}
+
+impl ResolveSpans for LdfEventTriggeredFrame {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+        self.references.resolve_spans(index);
+    }
+}
+
+/*
+Event_triggered_frames {
+  ETF_Doors: Collision_Doors, 0x3A, DoorLeft, DoorRight ;
+  ETF_Switches: 0x3B, SwitchLeft, SwitchRight ;
+}
+*/
+
+pub fn parse_ldf_event_triggered_frames(s: &str) -> IResult<&str, Vec<LdfEventTriggeredFrame>> {
+    // `Event_triggered_frames {` or `Event_triggered_frames{` or ...
+    // - May be any number of spaces before and after the "Event_triggered_frames" tag
+    // - May be any number of spaces before and after the opening curly brace
+    let (s, _) = skip_whitespace(s)?;
+
+    // Assume that if the section label is not present, then the section is not present.
+    if !s.starts_with("Event_triggered_frames") {
+        return Ok((s, Vec::new()));
+    }
+
+    let (s, _) = tag("Event_triggered_frames")(s)?;
+    let (s, _) = skip_whitespace(s)?;
+    let (s, _) = tag("{")(s)?;
+    let (s, _) = skip_whitespace(s)?;
+
+    let mut event_triggered_frames = Vec::new();
+    let mut remaining = s;
+
+    while !remaining.starts_with('}') {
+        let (s, event_triggered_frame) = parse_ldf_event_triggered_frame(remaining)?;
+        let (s, _) = skip_whitespace(s)?;
+        remaining = s;
+        event_triggered_frames.push(event_triggered_frame);
+    }
+
+    let (remaining, _) = tag("}")(remaining)?;
+
+    Ok((remaining, event_triggered_frames))
+}
+
+fn parse_ldf_event_triggered_frame(s: &str) -> IResult<&str, LdfEventTriggeredFrame> {
+    // `ETF_Doors: Collision_Doors, 0x3A, DoorLeft, DoorRight ;` or `ETF_Switches: 0x3B, SwitchLeft ;` or ...
+    // - May be any number of spaces before and after the event triggered frame name
+    // - May be any number of spaces before and after the colon
+    // - May be any number of spaces before and after the collision resolving schedule table (optional)
+    // - May be any number of spaces before and after the frame ID
+    // - May be any number of spaces before and after each associated frame name
+    // - May be any number of spaces before and after the semicolon
+    let (s, _) = skip_whitespace(s)?;
+    let entry = s;
+    let (s, frame_name) = parse_identifier(s)?;
+    let (s, _) = skip_whitespace(s)?;
+    let (s, _) = tag(":")(s)?;
+    let (s, _) = skip_whitespace(s)?;
+
+    // The frame ID always starts with a digit, the schedule table name never does
+    let mut references = Vec::new();
+    let (s, collision_resolving_schedule_table) = if s.starts_with(|c: char| c.is_ascii_digit()) {
+        (s, None)
+    } else {
+        let (s, schedule_table) = parse_identifier(s)?;
+        references.push(LdfNamedSpan::of(entry, schedule_table));
+        let (s, _) = skip_whitespace(s)?;
+        let (s, _) = tag(",")(s)?;
+        let (s, _) = skip_whitespace(s)?;
+        (s, Some(schedule_table.to_string()))
+    };
+
+    let (s, frame_id) = parse_integer(s)?;
+    let (s, _) = skip_whitespace(s)?;
+    let (s, _) = tag(",")(s)?;
+    let (s, associated_frames) = parse_identifier_list(s)?;
+    let (s, _) = tag(";")(s)?;
+
+    references.extend(associated_frames.iter().map(|frame| LdfNamedSpan::of(entry, frame)));
+    let associated_frames = associated_frames.iter().map(|frame| frame.to_string()).collect();
+
+    Ok((
+        s,
+        LdfEventTriggeredFrame {
+            frame_name: frame_name.to_string(),
+            frame_id,
+            collision_resolving_schedule_table,
+            associated_frames,
+            span: LdfSpan::new(entry, s),
+            references,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ldf_event_triggered_frames() {
+        let input = r#"
+            Event_triggered_frames {
+                ETF_Doors: Collision_Doors, 0x3A, DoorLeft, DoorRight ;
+                ETF_Switches : 59, SwitchLeft ;
+            }
+        "#;
+
+        let (_, event_triggered_frames) = parse_ldf_event_triggered_frames(input).unwrap();
+        assert_eq!(event_triggered_frames.len(), 2);
+
+        assert_eq!(event_triggered_frames[0].frame_name, "ETF_Doors");
+        assert_eq!(event_triggered_frames[0].frame_id, 0x3A);
+        assert_eq!(
+            event_triggered_frames[0].collision_resolving_schedule_table,
+            Some("Collision_Doors".to_string())
+        );
+        assert_eq!(
+            event_triggered_frames[0].associated_frames,
+            vec!["DoorLeft", "DoorRight"]
+        );
+
+        assert_eq!(event_triggered_frames[1].frame_name, "ETF_Switches");
+        assert_eq!(event_triggered_frames[1].frame_id, 59);
+        assert_eq!(event_triggered_frames[1].collision_resolving_schedule_table, None);
+        assert_eq!(event_triggered_frames[1].associated_frames, vec!["SwitchLeft"]);
+    }
+
+    #[test]
+    fn test_parse_ldf_event_triggered_frames_not_present() {
+        let input = r#"
+            Diagnostic_frames {
+            }
+        "#;
+
+        let (s, event_triggered_frames) = parse_ldf_event_triggered_frames(input).unwrap();
+        assert_eq!(event_triggered_frames.len(), 0);
+        assert!(s.starts_with("Diagnostic_frames"));
+    }
+
+    #[test]
+    fn test_parse_ldf_event_triggered_frames_comments() {
+        let input = r#"
+            Event_triggered_frames {
+                ETF_Doors: /* resolver */ Collision_Doors, 0x3A, DoorLeft, // left door
+                    DoorRight /* right door */ ;
+            }
+        "#;
+
+        let (_, event_triggered_frames) = parse_ldf_event_triggered_frames(input).unwrap();
+        assert_eq!(
+            event_triggered_frames[0].collision_resolving_schedule_table,
+            Some("Collision_Doors".to_string())
+        );
+        assert_eq!(
+            event_triggered_frames[0].associated_frames,
+            vec!["DoorLeft", "DoorRight"]
+        );
+        let references: Vec<&str> = event_triggered_frames[0]
+            .references
+            .iter()
+            .map(|reference| reference.name.as_str())
+            .collect();
+        assert_eq!(references, vec!["Collision_Doors", "DoorLeft", "DoorRight"]);
+
+        let input = r#"
+            Event_triggered_frames {
+                ETF_Doors: 0x3A, DoorLeft, ;
+            }
+        "#;
+        assert!(parse_ldf_event_triggered_frames(input).is_err());
+    }
+}
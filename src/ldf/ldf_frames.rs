@@ -1,8 +1,8 @@
 use crate::ldf::ldf_comment::skip_whitespace;
-use nom::{
-    bytes::complete::{tag, take_while},
-    AsChar, IResult,
-};
+use crate::ldf::ldf_error::{tag, IResult};
+use crate::ldf::ldf_number::parse_integer;
+use crate::ldf::ldf_span::{LdfNamedSpan, LdfSpan, LineIndex, ResolveSpans};
+use nom::bytes::complete::take_while;
 
 /// `Frames` section of a LIN Description File (LDF)
 /// ```text
@@ -35,6 +35,16 @@ pub struct LdfFrame {
 
     /// Frame signals
     pub signals: Vec<LdfFrameSignal>,
+
+    /// Location of the frame in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
+
+    /// Identifiers referenced by the frame (publisher), with their location in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub references: Vec<LdfNamedSpan>,
 }
 
 /// One signal section of a Frame in a LIN Description File (LDF).
@@ -59,6 +69,31 @@ pub struct LdfFrameSignal {
 
     /// Signal start bit
     pub start_bit: u8,
+
+    /// Location of the signal entry in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
+
+    /// Identifiers referenced by the signal entry (signal), with their location in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub references: Vec<LdfNamedSpan>,
+}
+
+impl ResolveSpans for LdfFrame {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+        self.references.resolve_spans(index);
+        self.signals.resolve_spans(index);
+    }
+}
+
+impl ResolveSpans for LdfFrameSignal {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+        self.references.resolve_spans(index);
+    }
 }
 
 /*
@@ -75,6 +110,24 @@ Frames {
 */
 
 pub fn parse_ldf_frames(s: &str) -> IResult<&str, Vec<LdfFrame>> {
+    parse_frames(s, false)
+}
+
+/// `Frames` section of a LIN 1.3 LDF, where the frame size may be left out:
+/// ```text
+/// Frames {
+///    Frame1: 0x10, Master {
+///       Signal1, 0 ;
+///    }
+/// }
+/// ```
+/// A missing frame size is derived from the frame ID as defined by LIN 1.3
+/// (0x00-0x1F: 2 bytes, 0x20-0x2F: 4 bytes, 0x30-0x3F: 8 bytes).
+pub fn parse_ldf_lin13_frames(s: &str) -> IResult<&str, Vec<LdfFrame>> {
+    parse_frames(s, true)
+}
+
+fn parse_frames(s: &str, lin13: bool) -> IResult<&str, Vec<LdfFrame>> {
     let (s, _) = skip_whitespace(s)?;
     let (s, _) = tag("Frames")(s)?;
     let (s, _) = skip_whitespace(s)?;
@@ -85,7 +138,7 @@ pub fn parse_ldf_frames(s: &str) -> IResult<&str, Vec<LdfFrame>> {
     let mut remaining = s;
 
     while !remaining.starts_with("}") {
-        let (s, frame) = parse_ldf_frame(remaining)?;
+        let (s, frame) = parse_ldf_frame(remaining, lin13)?;
         let (s, _) = skip_whitespace(s)?;
         remaining = s;
         frames.push(frame);
@@ -95,22 +148,30 @@ pub fn parse_ldf_frames(s: &str) -> IResult<&str, Vec<LdfFrame>> {
     Ok((remaining, frames))
 }
 
-fn parse_ldf_frame(s: &str) -> IResult<&str, LdfFrame> {
+fn parse_ldf_frame(s: &str, lin13: bool) -> IResult<&str, LdfFrame> {
     let (s, _) = skip_whitespace(s)?;
+    let entry = s;
     let (s, frame_name) = take_while(|c: char| c.is_alphanumeric() || c == '_')(s)?;
     let (s, _) = skip_whitespace(s)?;
     let (s, _) = tag(":")(s)?;
     let (s, _) = skip_whitespace(s)?;
-    let (s, frame_id) = take_while(|c: char| c.is_hex_digit() || c == 'x')(s)?;
+    let (s, frame_id) = parse_integer(s)?;
     let (s, _) = skip_whitespace(s)?;
     let (s, _) = tag(",")(s)?;
     let (s, _) = skip_whitespace(s)?;
     let (s, published_by) = take_while(|c: char| c.is_alphanumeric() || c == '_')(s)?;
     let (s, _) = skip_whitespace(s)?;
-    let (s, _) = tag(",")(s)?;
-    let (s, _) = skip_whitespace(s)?;
-    let (s, frame_size) = take_while(|c: char| c.is_numeric())(s)?;
-    let (s, _) = skip_whitespace(s)?;
+
+    // LIN 1.3 files may leave out the frame size
+    let (s, frame_size) = if lin13 && s.starts_with('{') {
+        (s, lin13_frame_size(frame_id))
+    } else {
+        let (s, _) = tag(",")(s)?;
+        let (s, _) = skip_whitespace(s)?;
+        let (s, frame_size) = parse_integer(s)?;
+        let (s, _) = skip_whitespace(s)?;
+        (s, frame_size)
+    };
     let (s, _) = tag("{")(s)?;
     let (s, _) = skip_whitespace(s)?;
 
@@ -129,27 +190,33 @@ fn parse_ldf_frame(s: &str) -> IResult<&str, LdfFrame> {
         remaining,
         LdfFrame {
             frame_name: frame_name.to_string(),
-            frame_id: {
-                if frame_id.starts_with("0x") {
-                    u8::from_str_radix(&frame_id[2..], 16).unwrap()
-                } else {
-                    frame_id.parse().unwrap()
-                }
-            },
+            frame_id,
             published_by: published_by.to_string(),
-            frame_size: frame_size.parse().unwrap(),
+            frame_size,
             signals,
+            span: LdfSpan::new(entry, remaining),
+            references: vec![LdfNamedSpan::of(entry, published_by)],
         },
     ))
 }
 
+/// Frame size in bytes implied by the identifier in LIN 1.3
+fn lin13_frame_size(frame_id: u8) -> u8 {
+    match frame_id {
+        0x00..=0x1F => 2,
+        0x20..=0x2F => 4,
+        _ => 8,
+    }
+}
+
 fn parse_ldf_frame_signal(s: &str) -> IResult<&str, LdfFrameSignal> {
     let (s, _) = skip_whitespace(s)?;
+    let entry = s;
     let (s, signal_name) = take_while(|c: char| c.is_alphanumeric() || c == '_')(s)?;
     let (s, _) = skip_whitespace(s)?;
     let (s, _) = tag(",")(s)?;
     let (s, _) = skip_whitespace(s)?;
-    let (s, start_bit) = take_while(|c: char| c.is_numeric())(s)?;
+    let (s, start_bit) = parse_integer(s)?;
     let (s, _) = skip_whitespace(s)?;
     let (s, _) = tag(";")(s)?;
 
@@ -157,7 +224,9 @@ fn parse_ldf_frame_signal(s: &str) -> IResult<&str, LdfFrameSignal> {
         s,
         LdfFrameSignal {
             signal_name: signal_name.to_string(),
-            start_bit: start_bit.parse().unwrap(),
+            start_bit,
+            span: LdfSpan::new(entry, s),
+            references: vec![LdfNamedSpan::of(entry, signal_name)],
         },
     ))
 }
@@ -203,4 +272,43 @@ mod tests {
         assert_eq!(frames[1].signals[1].signal_name, "Signal4");
         assert_eq!(frames[1].signals[1].start_bit, 10);
     }
+
+    #[test]
+    fn test_parse_ldf_lin13_frames() {
+        let input = r#"
+        Frames {
+            Frame1: 0x10, Master {
+                Signal1, 0 ;
+            }
+            Frame2: 0x21, Slave1 {
+                Signal2, 0 ;
+            }
+            Frame3: 0x30, Slave1, 4 {
+                Signal3, 0 ;
+            }
+        }
+        "#;
+
+        let (_, frames) = parse_ldf_lin13_frames(input).unwrap();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].frame_size, 2);
+        assert_eq!(frames[1].frame_size, 4);
+        assert_eq!(frames[1].published_by, "Slave1");
+        assert_eq!(frames[2].frame_size, 4);
+
+        // The frame size is required in LIN 2.x
+        assert!(parse_ldf_frames(input).is_err());
+    }
+
+    #[test]
+    fn test_parse_ldf_frames_invalid_numbers() {
+        let frame_id_too_large = "Frames { Frame1: 0x1FF, Master, 8 { Signal1, 0 ; } }";
+        assert!(parse_ldf_frames(frame_id_too_large).is_err());
+
+        let frame_size_missing = "Frames { Frame1: 0x10, Master, { Signal1, 0 ; } }";
+        assert!(parse_ldf_frames(frame_size_missing).is_err());
+
+        let start_bit_too_large = "Frames { Frame1: 0x10, Master, 8 { Signal1, 256 ; } }";
+        assert!(parse_ldf_frames(start_bit_too_large).is_err());
+    }
 }
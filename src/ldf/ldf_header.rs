@@ -1,9 +1,7 @@
-use nom::{
-    bytes::complete::{tag, take_until, take_while},
-    IResult,
-};
-
 use crate::ldf::ldf_comment::skip_whitespace;
+use crate::ldf::ldf_error::{tag, take_until, IResult, LdfParseError};
+use crate::ldf::ldf_number::parse_real;
+use nom::{bytes::complete::take_while, error::ErrorKind};
 
 /// Header of a LIN Description File (LDF) for LIN
 /// ```text
@@ -13,26 +11,49 @@ use crate::ldf::ldf_comment::skip_whitespace;
 /// LIN_speed = 19.2 kbps ;
 /// Channel_name = "DB";
 /// ```
+#[derive(Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
 pub struct LdfHeader {
     /// LIN protocol version number (e.g. 2.1).
-    /// Shall be in the range of "0.01" to "99.99".
+    /// Shall be in the range of "0.01" to "99.99", optionally followed by a revision letter (e.g. "2.2A").
+    /// SAE J2602 files use a version like "J2602_1_1.0" and ISO 17987 files one like "ISO17987:2015" instead.
+    /// Other forms are rejected when parsing, unknown numbers (e.g. "3.0") are reported by the validation.
     pub lin_protocol_version: String,
 
-    /// LIN language version number (e.g. 2.1)
-    /// Shall be in the range of "0.01" to "99.99".
+    /// LIN language version number (e.g. 2.1), in the same forms as the protocol version.
+    /// SAE J2602 files use a version like "J2602_3_1.0" instead.
     pub lin_language_version: String,
 
     /// LIN speed in bits per second (e.g. 19.2 kbps = 19200).
-    /// This sets the nominal bit rate for the cluster. It shall be in the range of 1 to 20 kbit/second.
+    /// This sets the nominal bit rate for the cluster. It is above 0 and at most 65.535 kbit/second.
     pub lin_speed: u16,
 
     /// Channel_name is optional and was added in LIN 2.2 version.
     pub channel_name: Option<String>,
 }
 
+impl LdfHeader {
+    /// Whether the file follows the LIN 1.3 (or older) specification, which has its own grammar
+    /// for a few sections (e.g. no `Node_attributes`, optional frame sizes and `Dynamic_frames`).
+    pub fn is_lin13(&self) -> bool {
+        self.lin_protocol_version.starts_with("1.")
+    }
+
+    /// Whether the file follows the SAE J2602 dialect of the LIN specification
+    /// (e.g. `LIN_protocol_version = "J2602_1_1.0" ;`).
+    pub fn is_j2602(&self) -> bool {
+        self.lin_protocol_version.starts_with("J2602")
+    }
+
+    /// Whether the file follows ISO 17987, the standardized successor of LIN 2.2A
+    /// (e.g. `LIN_protocol_version = "ISO17987:2015" ;`).
+    pub fn is_iso17987(&self) -> bool {
+        self.lin_protocol_version.starts_with("ISO17987") || self.lin_language_version.starts_with("ISO17987")
+    }
+}
+
 /*
 LIN_description_file;
 LIN_protocol_version = "2.1" ;
@@ -62,16 +83,13 @@ pub fn parse_ldf_header(s: &str) -> IResult<&str, LdfHeader> {
     let (s, _) = skip_whitespace(s)?;
     let (s, _) = tag("=")(s)?;
     let (s, _) = skip_whitespace(s)?;
+    // - May be a SAE J2602 (e.g. "J2602_1_1.0") or ISO 17987 (e.g. "ISO17987:2015") version instead of a number
     let (s, _) = tag("\"")(s)?;
-    let (s, protocol_major) = take_while(|c: char| c.is_numeric())(s)?;
-    let (s, _) = tag(".")(s)?;
-    let (s, protocol_minor) = take_while(|c: char| c.is_numeric())(s)?;
+    let (s, lin_protocol_version) = parse_version(s)?;
     let (s, _) = tag("\"")(s)?;
     let (s, _) = skip_whitespace(s)?;
     let (s, _) = tag(";")(s)?;
 
-    let lin_protocol_version = format!("{}.{}", protocol_major, protocol_minor);
-
     // `LIN_language_version = "2.1" ;` or `LIN_language_version = "2.1";` or ...
     // - May be any number of spaces before and after the "LIN_language_version" tag
     // - May be any number of spaces before and after the equal sign
@@ -82,16 +100,13 @@ pub fn parse_ldf_header(s: &str) -> IResult<&str, LdfHeader> {
     let (s, _) = skip_whitespace(s)?;
     let (s, _) = tag("=")(s)?;
     let (s, _) = skip_whitespace(s)?;
+    // - May be a SAE J2602 (e.g. "J2602_3_1.0") or ISO 17987 (e.g. "ISO17987:2015") version instead of a number
     let (s, _) = tag("\"")(s)?;
-    let (s, language_major) = take_while(|c: char| c.is_numeric())(s)?;
-    let (s, _) = tag(".")(s)?;
-    let (s, language_minor) = take_while(|c: char| c.is_numeric())(s)?;
+    let (s, lin_language_version) = parse_version(s)?;
     let (s, _) = tag("\"")(s)?;
     let (s, _) = skip_whitespace(s)?;
     let (s, _) = tag(";")(s)?;
 
-    let lin_language_version = format!("{}.{}", language_major, language_minor);
-
     // LIN_speed = 19.2 kbps ;
     // - May be any number of spaces before and after the "LIN_speed" tag
     // - May be any number of spaces before and after the equal sign
@@ -102,15 +117,20 @@ pub fn parse_ldf_header(s: &str) -> IResult<&str, LdfHeader> {
     let (s, _) = tag("LIN_speed")(s)?;
     let (s, _) = skip_whitespace(s)?;
     let (s, _) = tag("=")(s)?;
+    // - Must be above 0 and at most 65.535 kbps, the speed is stored in bit/s
     let (s, _) = skip_whitespace(s)?;
-    let (s, lin_speed) = take_while(|c: char| c.is_numeric() || c == '.')(s)?;
+    let speed_start = s;
+    let (s, lin_speed) = parse_real(s)?;
+    let lin_speed = (lin_speed * 1000.0).round();
+    if !(lin_speed > 0.0 && lin_speed <= f64::from(u16::MAX)) {
+        return Err(nom::Err::Error(LdfParseError::new(speed_start, ErrorKind::MapRes)));
+    }
+    let lin_speed = lin_speed as u16;
     let (s, _) = skip_whitespace(s)?;
     let (s, _) = tag("kbps")(s)?;
     let (s, _) = skip_whitespace(s)?;
     let (s, _) = tag(";")(s)?;
 
-    let lin_speed = (lin_speed.parse::<f32>().unwrap() * 1000.0) as u16;
-
     let (s, channel_name) = parse_channel_name(s).unwrap_or((s, None));
 
     Ok((
@@ -124,6 +144,37 @@ pub fn parse_ldf_header(s: &str) -> IResult<&str, LdfHeader> {
     ))
 }
 
+/// Quoted version of the header, without the quotes (`2.1`, `2.2A`, `J2602_1_1.0`, `ISO17987:2015`)
+fn parse_version(s: &str) -> IResult<&str, String> {
+    let (remaining, version) = take_until("\"")(s)?;
+    let version = version.trim();
+    if !is_version(version) {
+        return Err(nom::Err::Error(LdfParseError::new(s, ErrorKind::Verify)));
+    }
+    Ok((remaining, version.to_string()))
+}
+
+/// Whether `version` is written like a LIN, SAE J2602 or ISO 17987 version
+fn is_version(version: &str) -> bool {
+    let digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    // `2.1` or `2.2A`
+    let number = |s: &str| {
+        let s = s.strip_suffix(|c: char| c.is_ascii_uppercase()).unwrap_or(s);
+        s.split_once('.')
+            .is_some_and(|(major, minor)| digits(major) && digits(minor))
+    };
+
+    if let Some(rest) = version.strip_prefix("J2602_") {
+        // `J2602_<part>_<version>`
+        rest.split_once('_')
+            .is_some_and(|(part, rest)| digits(part) && number(rest))
+    } else if let Some(year) = version.strip_prefix("ISO17987:") {
+        digits(year) && year.len() == 4
+    } else {
+        number(version)
+    }
+}
+
 pub fn parse_channel_name(s: &str) -> IResult<&str, Option<String>> {
     // Channel_name = "DB";
     // - May be any number of spaces before and after the "Channel_name" tag
@@ -147,6 +198,7 @@ pub fn parse_channel_name(s: &str) -> IResult<&str, Option<String>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ldf::ldf_error::LdfError;
 
     #[test]
     fn test_parse() {
@@ -187,4 +239,121 @@ mod tests {
         assert_eq!(header.lin_speed, 19200);
         assert_eq!(header.channel_name, None);
     }
+
+    #[test]
+    fn test_parse_lin_speed_out_of_range() {
+        let header = |speed: &str| {
+            format!(
+                r#"
+                LIN_description_file ;
+                LIN_protocol_version = "2.1" ;
+                LIN_language_version = "2.1" ;
+                LIN_speed = {} kbps ;
+                "#,
+                speed
+            )
+        };
+
+        let (_, parsed) = parse_ldf_header(&header("65.535")).unwrap();
+        assert_eq!(parsed.lin_speed, u16::MAX);
+
+        for speed in ["100", "-5", "0", "1E999"] {
+            let s = header(speed);
+            let error = parse_ldf_header(&s).err().unwrap();
+            let error = LdfError::from_parse_error(&s, "header", error);
+            assert_eq!(error.message, "value out of range", "LIN_speed = {} kbps", speed);
+            assert_eq!(&s[error.offset..error.offset + speed.len()], speed);
+        }
+    }
+
+    #[test]
+    fn test_parse_versions() {
+        let header = |version: &str| {
+            format!(
+                r#"
+                LIN_description_file ;
+                LIN_protocol_version = "{}" ;
+                LIN_language_version = "2.1" ;
+                LIN_speed = 19.2 kbps ;
+                "#,
+                version
+            )
+        };
+
+        for version in ["1.3", "2.2A", "3.0", "J2602_1_2.0", "ISO17987:2016"] {
+            let (_, parsed) = parse_ldf_header(&header(version)).unwrap();
+            assert_eq!(parsed.lin_protocol_version, version);
+        }
+
+        for version in [
+            "",
+            "abc",
+            "2",
+            "2.x",
+            "2.2AB",
+            "J2602_1.0",
+            "J2602_1_x",
+            "ISO17987",
+            "ISO17987:15",
+        ] {
+            let s = header(version);
+            let error = parse_ldf_header(&s).err().unwrap();
+            let error = LdfError::from_parse_error(&s, "header", error);
+            assert_eq!(
+                error.message, "invalid or incomplete definition",
+                "LIN_protocol_version = \"{}\"",
+                version
+            );
+            assert_eq!(
+                error.hint().as_deref(),
+                Some("versions are written like `2.1`, `J2602_1_1.0` or `ISO17987:2015`")
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_lin13() {
+        let s = r#"
+            LIN_description_file ;
+            LIN_protocol_version = "1.3" ;
+            LIN_language_version = "1.3" ;
+            LIN_speed = 9.6 kbps ;
+        "#;
+
+        let (_, header) = parse_ldf_header(s).unwrap();
+        assert!(header.is_lin13());
+        assert_eq!(header.lin_speed, 9600);
+    }
+
+    #[test]
+    fn test_parse_j2602() {
+        let s = r#"
+            LIN_description_file ;
+            LIN_protocol_version = "J2602_1_1.0" ;
+            LIN_language_version = "J2602_3_1.0" ;
+            LIN_speed = 10.417 kbps ;
+        "#;
+
+        let (_, header) = parse_ldf_header(s).unwrap();
+        assert_eq!(header.lin_protocol_version, "J2602_1_1.0");
+        assert_eq!(header.lin_language_version, "J2602_3_1.0");
+        assert_eq!(header.lin_speed, 10417);
+        assert!(header.is_j2602());
+        assert!(!header.is_lin13());
+        assert!(!header.is_iso17987());
+    }
+
+    #[test]
+    fn test_is_iso17987() {
+        let s = r#"
+            LIN_description_file ;
+            LIN_protocol_version = "ISO17987:2015" ;
+            LIN_language_version = "ISO17987:2015" ;
+            LIN_speed = 19.2 kbps ;
+        "#;
+
+        let (_, header) = parse_ldf_header(s).unwrap();
+        assert!(header.is_iso17987());
+        assert!(!header.is_lin13());
+    }
 }
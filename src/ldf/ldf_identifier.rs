@@ -0,0 +1,51 @@
+use crate::ldf::ldf_comment::skip_whitespace;
+use crate::ldf::ldf_error::{tag, IResult};
+use nom::bytes::complete::take_while1;
+
+/// Identifier such as a node, signal, frame or schedule table name (`Frame1`, `ETF_Doors`).
+pub fn parse_identifier(s: &str) -> IResult<&str, &str> {
+    take_while1(|c: char| c.is_alphanumeric() || c == '_')(s)
+}
+
+/// Comma separated list of identifiers (`Frame1, Frame2`), with whitespace and comments allowed around each
+/// identifier and comma. The identifiers are slices of `s`, so their location in the file can be looked up.
+pub fn parse_identifier_list(s: &str) -> IResult<&str, Vec<&str>> {
+    let (s, _) = skip_whitespace(s)?;
+    let (s, identifier) = parse_identifier(s)?;
+    let (mut remaining, _) = skip_whitespace(s)?;
+
+    let mut identifiers = vec![identifier];
+    while remaining.starts_with(',') {
+        let (s, _) = tag(",")(remaining)?;
+        let (s, _) = skip_whitespace(s)?;
+        let (s, identifier) = parse_identifier(s)?;
+        let (s, _) = skip_whitespace(s)?;
+        identifiers.push(identifier);
+        remaining = s;
+    }
+
+    Ok((remaining, identifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_identifier() {
+        assert_eq!(parse_identifier("Frame_1 ;"), Ok((" ;", "Frame_1")));
+        assert!(parse_identifier(" Frame1").is_err());
+        assert!(parse_identifier(";").is_err());
+    }
+
+    #[test]
+    fn test_parse_identifier_list() {
+        assert_eq!(parse_identifier_list("Frame1 ;"), Ok((";", vec!["Frame1"])));
+        assert_eq!(
+            parse_identifier_list("Frame1, /* left */ Frame2 // right\n , Frame3;"),
+            Ok((";", vec!["Frame1", "Frame2", "Frame3"]))
+        );
+        assert!(parse_identifier_list(" ;").is_err());
+        assert!(parse_identifier_list("Frame1, ;").is_err());
+    }
+}
@@ -1,8 +1,9 @@
 use crate::ldf::ldf_comment::skip_whitespace;
-use nom::{
-    bytes::complete::{tag, take_while},
-    AsChar, IResult,
-};
+use crate::ldf::ldf_error::{tag, take_until, IResult, LdfParseError};
+use crate::ldf::ldf_identifier::{parse_identifier, parse_identifier_list};
+use crate::ldf::ldf_number::{parse_integer, parse_milliseconds, parse_real};
+use crate::ldf::ldf_span::{LdfNamedSpan, LdfSpan, LineIndex, ResolveSpans};
+use nom::error::ErrorKind;
 
 /// `Node_attributes` section of a LIN Description File (LDF)
 /// ```text
@@ -39,6 +40,10 @@ use nom::{
 ///   }
 /// }
 /// ```
+///
+/// The attributes of a node may be given in any order, but each at most once. Only `LIN_protocol`,
+/// `configured_NAD` and `product_id` are required, all other attributes are optional. Attributes that are
+/// not part of the specification (e.g. vendor specific ones) are kept in `unknown_attributes`.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
@@ -52,8 +57,8 @@ pub struct LdfNodeAttributes {
     /// Configured NAD
     pub configured_nad: u8,
 
-    /// Initial NAD
-    pub initial_nad: u8,
+    /// Initial NAD (optional, defaults to the configured NAD)
+    pub initial_nad: Option<u8>,
 
     /// Part of the product ID
     pub supplier_id: u16,
@@ -61,28 +66,156 @@ pub struct LdfNodeAttributes {
     /// Part of the product ID
     pub function_id: u16,
 
-    /// Variant of the product ID
-    pub variant: u8,
+    /// Variant of the product ID (optional)
+    pub variant: Option<u8>,
 
-    /// Response error
-    pub response_error: String,
+    /// Response error signal
+    pub response_error: Option<String>,
 
-    /// P2_min
-    pub p2_min: String,
+    /// Signals the node sets to their init value when it detects a fault (optional)
+    pub fault_state_signals: Vec<String>,
 
-    /// ST_min
-    pub st_min: String,
+    /// P2_min (optional, the specification default is 50 ms)
+    pub p2_min: Option<String>,
 
-    /// N_As_timeout
-    pub n_as_timeout: String,
+    /// ST_min (optional, the specification default is 0 ms)
+    pub st_min: Option<String>,
 
-    /// N_Cr_timeout
-    pub n_cr_timeout: String,
+    /// N_As_timeout (optional, the specification default is 1000 ms)
+    pub n_as_timeout: Option<String>,
+
+    /// N_Cr_timeout (optional, the specification default is 1000 ms)
+    pub n_cr_timeout: Option<String>,
+
+    /// Response tolerance in percent (optional, SAE J2602)
+    pub response_tolerance: Option<f32>,
+
+    /// Time the node needs to be ready after a wake up (optional, SAE J2602)
+    pub wakeup_time: Option<String>,
+
+    /// Time the node needs to be ready after power on (optional, SAE J2602)
+    pub poweron_time: Option<String>,
 
     /// Configurable frames
-    pub configurable_frames: Vec<String>,
+    pub configurable_frames: Vec<LdfConfigurableFrame>,
+
+    /// Attributes that are not part of the specification, e.g. vendor specific ones
+    pub unknown_attributes: Vec<LdfUnknownAttribute>,
+
+    /// Location of the node attributes in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
+
+    /// Identifiers referenced by the node attributes (node, response error signal, fault state signals), with their location in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub references: Vec<LdfNamedSpan>,
+}
+
+/// Configurable frame of a node in the `Node_attributes` section of a LIN Description File (LDF)
+/// ```text
+/// configurable_frames {
+///    Frame1 ;            // LIN 2.1 and later
+///    Frame2 = 0x1002 ;   // LIN 2.0
+/// }
+/// ```
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub struct LdfConfigurableFrame {
+    /// Frame name
+    pub frame_name: String,
+
+    /// 16 bit message identifier used by `AssignFrameId` (LIN 2.0 only)
+    pub message_id: Option<u16>,
+
+    /// Location of the configurable frame entry in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
+
+    /// Identifiers referenced by the configurable frame entry (frame), with their location in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub references: Vec<LdfNamedSpan>,
 }
 
+/// Attribute of a node in the `Node_attributes` section that is not part of the specification
+/// ```text
+/// Supplier_setting = "Value" ;
+/// ```
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub struct LdfUnknownAttribute {
+    /// Attribute name, e.g. `Supplier_setting`
+    pub name: String,
+
+    /// Raw text between the equal sign and the semicolon, without surrounding whitespace
+    pub value: String,
+
+    /// Location of the attribute in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
+}
+
+impl ResolveSpans for LdfNodeAttributes {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+        self.references.resolve_spans(index);
+        self.configurable_frames.resolve_spans(index);
+        self.unknown_attributes.resolve_spans(index);
+    }
+}
+
+impl ResolveSpans for LdfUnknownAttribute {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+    }
+}
+
+impl ResolveSpans for LdfConfigurableFrame {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+        self.references.resolve_spans(index);
+    }
+}
+
+/*
+Node_attributes {
+  Slave1{
+    LIN_protocol = "2.1" ;
+    configured_NAD = 0xB ;
+    initial_NAD = 0xB ;
+    product_id = 0x123, 0x4567, 8 ;
+    response_error = Signal1 ;
+    fault_state_signals = Signal2, Signal3 ;
+    P2_min = 100 ms ;
+    ST_min = 0 ms ;
+    N_As_timeout = 1000 ms ;
+    N_Cr_timeout = 1000 ms ;
+    response_tolerance = 38 % ;
+    configurable_frames {
+       Frame1 ;
+       Frame2 ;
+    }
+  }
+  Slave2{
+    LIN_protocol = "2.0" ;
+    configured_NAD = 0xC ;
+    product_id = 0x124, 0x4568 ;
+    configurable_frames {
+       Frame1 = 0x1001 ;
+       Frame2 = 0x1002 ;
+    }
+  }
+}
+*/
+
 pub fn parse_ldf_node_attributes(s: &str) -> IResult<&str, Vec<LdfNodeAttributes>> {
     // `Node_attributes {` or `Node_attributes{` or ...
     // - May be any number of spaces before and after the "Node_attributes" tag
@@ -91,188 +224,295 @@ pub fn parse_ldf_node_attributes(s: &str) -> IResult<&str, Vec<LdfNodeAttributes
     let (s, _) = tag("Node_attributes")(s)?;
     let (s, _) = skip_whitespace(s)?;
     let (s, _) = tag("{")(s)?;
+    let (s, _) = skip_whitespace(s)?;
 
     let mut node_attributes = Vec::new();
     let mut remaining = s;
 
     while !remaining.starts_with('}') {
-        // `Slave1{` or `Slave1 {` or ...
-        // - May be any number of spaces before and after the node name
-        // - May be any number of spaces before and after the opening curly brace
-        let (s, _) = skip_whitespace(remaining)?;
-        let (s, node_name) = take_while(|c: char| c.is_alphanumeric() || c == '_')(s)?;
+        let (s, node) = parse_ldf_node_attributes_node(remaining)?;
         let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("{")(s)?;
+        remaining = s;
+        node_attributes.push(node);
+    }
 
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("LIN_protocol")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("=")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("\"")(s)?;
-        let (s, lin_protocol) = take_while(|c: char| c.is_numeric() || c == '.')(s)?;
-        let (s, _) = tag("\"")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag(";")(s)?;
+    let (remaining, _) = tag("}")(remaining)?;
 
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("configured_NAD")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("=")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, configured_nad) = take_while(|c: char| c.is_hex_digit() || c == 'x')(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag(";")(s)?;
+    Ok((remaining, node_attributes))
+}
 
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("initial_NAD")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("=")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, initial_nad) = take_while(|c: char| c.is_hex_digit() || c == 'x')(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag(";")(s)?;
+fn parse_ldf_node_attributes_node(s: &str) -> IResult<&str, LdfNodeAttributes> {
+    // `Slave1{` or `Slave1 {` or ...
+    // - May be any number of spaces before and after the node name
+    // - May be any number of spaces before and after the opening curly brace
+    let start = s;
+    let (s, node_name) = parse_identifier(s)?;
+    let (s, _) = skip_whitespace(s)?;
+    let (s, _) = tag("{")(s)?;
+    let (s, _) = skip_whitespace(s)?;
 
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("product_id")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("=")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, supplier_id) = take_while(|c: char| c.is_hex_digit() || c == 'x')(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag(",")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, function_id) = take_while(|c: char| c.is_hex_digit() || c == 'x')(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag(",")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, variant) = take_while(|c: char| c.is_hex_digit() || c == 'x')(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag(";")(s)?;
+    let mut lin_protocol = None;
+    let mut configured_nad = None;
+    let mut initial_nad = None;
+    let mut product_id = None;
+    let mut response_error = None;
+    let mut fault_state_signals = Vec::new();
+    let mut p2_min = None;
+    let mut st_min = None;
+    let mut n_as_timeout = None;
+    let mut n_cr_timeout = None;
+    let mut response_tolerance = None;
+    let mut wakeup_time = None;
+    let mut poweron_time = None;
+    let mut configurable_frames = Vec::new();
+    let mut unknown_attributes = Vec::new();
+    let mut references = vec![LdfNamedSpan::of(start, node_name)];
+    let mut parsed_attributes: Vec<&str> = Vec::new();
 
-        let (s, _) = skip_whitespace(s)?;
-        // response_error is the optional section
-        let mut response_error = "";
-        let res;
-        if s.starts_with("response_error") {
-            let (s, _) = tag("response_error")(s)?;
-            let (s, _) = skip_whitespace(s)?;
-            let (s, _) = tag("=")(s)?;
-            let (s, _) = skip_whitespace(s)?;
-            let (s, resp_err) = take_while(|c: char| c.is_alphanumeric() || c == '_')(s)?;
-            response_error = resp_err;
+    let mut remaining = s;
+
+    while !remaining.starts_with('}') {
+        // `<attribute> = <value> ;` or `configurable_frames { ... }`
+        // - May be any number of spaces before and after the attribute name
+        // - May be any number of spaces before and after the equal sign
+        // - May be any number of spaces before and after the value
+        // - May be any number of spaces before and after the semicolon
+        let entry = remaining;
+        let (s, attribute) = parse_identifier(remaining)?;
+        let (s, _) = skip_whitespace(s)?;
+
+        // Unknown attributes are not checked for duplicates, vendors may repeat them
+        if KNOWN_ATTRIBUTES.contains(&attribute) {
+            if parsed_attributes.contains(&attribute) {
+                return Err(nom::Err::Error(LdfParseError::new(entry, ErrorKind::Permutation)));
+            }
+            parsed_attributes.push(attribute);
+        }
+
+        if attribute == "configurable_frames" {
+            let (s, frames) = parse_configurable_frames(s)?;
+            configurable_frames = frames;
             let (s, _) = skip_whitespace(s)?;
-            let (s, _) = tag(";")(s)?;
-            res = s;
-        } else {
-            res = s;
+            remaining = s;
+            continue;
         }
-        let s = res;
 
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("P2_min")(s)?;
-        let (s, _) = skip_whitespace(s)?;
         let (s, _) = tag("=")(s)?;
         let (s, _) = skip_whitespace(s)?;
-        let (s, p2_min) = take_while(|c: char| c.is_numeric())(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("ms")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag(";")(s)?;
 
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("ST_min")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("=")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, st_min) = take_while(|c: char| c.is_numeric())(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("ms")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag(";")(s)?;
+        let s = match attribute {
+            "LIN_protocol" => {
+                let (s, _) = tag("\"")(s)?;
+                let (s, value) = take_until("\"")(s)?;
+                let (s, _) = tag("\"")(s)?;
+                lin_protocol = Some(value.to_string());
+                s
+            }
+            "configured_NAD" => {
+                let (s, value) = parse_integer(s)?;
+                configured_nad = Some(value);
+                s
+            }
+            "initial_NAD" => {
+                let (s, value) = parse_integer(s)?;
+                initial_nad = Some(value);
+                s
+            }
+            "product_id" => {
+                // `<supplier_id>, <function_id> [, <variant>]`
+                let (s, supplier_id) = parse_integer(s)?;
+                let (s, _) = skip_whitespace(s)?;
+                let (s, _) = tag(",")(s)?;
+                let (s, _) = skip_whitespace(s)?;
+                let (s, function_id) = parse_integer(s)?;
+                let (s, _) = skip_whitespace(s)?;
+                let (s, variant) = match s.strip_prefix(',') {
+                    Some(s) => {
+                        let (s, _) = skip_whitespace(s)?;
+                        let (s, variant) = parse_integer(s)?;
+                        (s, Some(variant))
+                    }
+                    None => (s, None),
+                };
+                product_id = Some((supplier_id, function_id, variant));
+                s
+            }
+            "response_error" => {
+                let (s, value) = parse_identifier(s)?;
+                response_error = Some(value.to_string());
+                references.push(LdfNamedSpan::of(start, value));
+                s
+            }
+            "fault_state_signals" => {
+                let (s, signals) = parse_identifier_list(s)?;
+                fault_state_signals = signals.iter().map(|signal| signal.to_string()).collect();
+                references.extend(signals.iter().map(|signal| LdfNamedSpan::of(start, signal)));
+                s
+            }
+            "P2_min" => {
+                let (s, value) = parse_milliseconds(s)?;
+                p2_min = Some(value);
+                s
+            }
+            "ST_min" => {
+                let (s, value) = parse_milliseconds(s)?;
+                st_min = Some(value);
+                s
+            }
+            "N_As_timeout" => {
+                let (s, value) = parse_milliseconds(s)?;
+                n_as_timeout = Some(value);
+                s
+            }
+            "N_Cr_timeout" => {
+                let (s, value) = parse_milliseconds(s)?;
+                n_cr_timeout = Some(value);
+                s
+            }
+            "wakeup_time" => {
+                let (s, value) = parse_milliseconds(s)?;
+                wakeup_time = Some(value);
+                s
+            }
+            "poweron_time" => {
+                let (s, value) = parse_milliseconds(s)?;
+                poweron_time = Some(value);
+                s
+            }
+            "response_tolerance" => {
+                let (s, value) = parse_real(s)?;
+                let (s, _) = skip_whitespace(s)?;
+                let (s, _) = tag("%")(s)?;
+                response_tolerance = Some(value as f32);
+                s
+            }
+            _ => {
+                let (s, value) = take_until(";")(s)?;
+                let (s, _) = tag(";")(s)?;
+                unknown_attributes.push(LdfUnknownAttribute {
+                    name: attribute.to_string(),
+                    value: value.trim().to_string(),
+                    span: LdfSpan::new(entry, s),
+                });
+                let (s, _) = skip_whitespace(s)?;
+                remaining = s;
+                continue;
+            }
+        };
 
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("N_As_timeout")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("=")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, n_as_timeout) = take_while(|c: char| c.is_numeric())(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("ms")(s)?;
         let (s, _) = skip_whitespace(s)?;
         let (s, _) = tag(";")(s)?;
-
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("N_Cr_timeout")(s)?;
         let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("=")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, n_cr_timeout) = take_while(|c: char| c.is_numeric())(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("ms")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag(";")(s)?;
-
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("configurable_frames")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("{")(s)?;
-        let (s, _) = skip_whitespace(s)?;
-
         remaining = s;
-        let mut configurable_frames = Vec::new();
+    }
 
-        while !remaining.starts_with('}') {
-            let (s, _) = skip_whitespace(remaining)?;
-            let (s, frame_name) = take_while(|c: char| c.is_alphanumeric() || c == '_')(s)?;
-            let (s, _) = skip_whitespace(s)?;
-            let (s, _) = tag(";")(s)?;
-            let (s, _) = skip_whitespace(s)?;
+    let (s, _) = tag("}")(remaining)?;
+
+    // `LIN_protocol`, `configured_NAD` and `product_id` are required
+    let missing = |attribute: &'static str| {
+        nom::Err::Error(LdfParseError {
+            input: start,
+            kind: ErrorKind::Verify,
+            expected: Some(attribute),
+        })
+    };
+    let lin_protocol = lin_protocol.ok_or_else(|| missing("LIN_protocol"))?;
+    let configured_nad = configured_nad.ok_or_else(|| missing("configured_NAD"))?;
+    let (supplier_id, function_id, variant) = product_id.ok_or_else(|| missing("product_id"))?;
+
+    Ok((
+        s,
+        LdfNodeAttributes {
+            node_name: node_name.to_string(),
+            lin_protocol,
+            configured_nad,
+            initial_nad,
+            supplier_id,
+            function_id,
+            variant,
+            response_error,
+            fault_state_signals,
+            p2_min,
+            st_min,
+            n_as_timeout,
+            n_cr_timeout,
+            response_tolerance,
+            wakeup_time,
+            poweron_time,
+            configurable_frames,
+            unknown_attributes,
+            span: LdfSpan::new(start, s),
+            references,
+        },
+    ))
+}
 
-            configurable_frames.push(frame_name.to_string());
+/// Attributes of a node defined by the specification
+const KNOWN_ATTRIBUTES: [&str; 14] = [
+    "LIN_protocol",
+    "configured_NAD",
+    "initial_NAD",
+    "product_id",
+    "response_error",
+    "fault_state_signals",
+    "P2_min",
+    "ST_min",
+    "N_As_timeout",
+    "N_Cr_timeout",
+    "response_tolerance",
+    "wakeup_time",
+    "poweron_time",
+    "configurable_frames",
+];
+
+fn parse_configurable_frames(s: &str) -> IResult<&str, Vec<LdfConfigurableFrame>> {
+    // `configurable_frames { Frame1 ; Frame2 ; }` or `configurable_frames { Frame1 = 0x1001 ; }`
+    // - May be any number of spaces before and after the curly braces
+    // - May be any number of spaces before and after each frame name
+    // - May be any number of spaces before and after the equal sign and message ID (LIN 2.0)
+    // - May be any number of spaces before and after each semicolon
+    let (s, _) = tag("{")(s)?;
+    let (s, _) = skip_whitespace(s)?;
 
-            remaining = s;
-        }
+    let mut configurable_frames = Vec::new();
+    let mut remaining = s;
 
-        let (s, _) = tag("}")(remaining)?;
-        let (s, _) = skip_whitespace(s)?;
-        let (s, _) = tag("}")(s)?;
+    while !remaining.starts_with('}') {
+        let entry = remaining;
+        let (s, frame_name) = parse_identifier(remaining)?;
+        let (s, _) = skip_whitespace(s)?;
+        let (s, message_id) = match s.strip_prefix('=') {
+            Some(s) => {
+                let (s, _) = skip_whitespace(s)?;
+                let (s, message_id) = parse_integer(s)?;
+                let (s, _) = skip_whitespace(s)?;
+                (s, Some(message_id))
+            }
+            None => (s, None),
+        };
+        let (s, _) = tag(";")(s)?;
+        let span = LdfSpan::new(entry, s);
         let (s, _) = skip_whitespace(s)?;
 
-        let node = LdfNodeAttributes {
-            node_name: node_name.to_string(),
-            lin_protocol: lin_protocol.to_string(),
-            configured_nad: u8::from_str_radix(&configured_nad[2..], 16).unwrap(),
-            initial_nad: u8::from_str_radix(&initial_nad[2..], 16).unwrap(),
-            supplier_id: u16::from_str_radix(&supplier_id[2..], 16).unwrap(),
-            function_id: u16::from_str_radix(&function_id[2..], 16).unwrap(),
-            variant: {
-                if variant.starts_with("0x") {
-                    u8::from_str_radix(&variant[2..], 16).unwrap()
-                } else {
-                    variant.parse().unwrap()
-                }
-            },
-            response_error: response_error.to_string(),
-            p2_min: p2_min.to_string() + " ms",
-            st_min: st_min.to_string() + " ms",
-            n_as_timeout: n_as_timeout.to_string() + " ms",
-            n_cr_timeout: n_cr_timeout.to_string() + " ms",
-            configurable_frames: configurable_frames,
-        };
+        configurable_frames.push(LdfConfigurableFrame {
+            frame_name: frame_name.to_string(),
+            message_id,
+            span,
+            references: vec![LdfNamedSpan::of(entry, frame_name)],
+        });
 
-        node_attributes.push(node);
         remaining = s;
     }
 
-    let (remaining, _) = tag("}")(remaining)?;
+    let (s, _) = tag("}")(remaining)?;
 
-    Ok((remaining, node_attributes))
+    Ok((s, configurable_frames))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ldf::ldf_error::LdfError;
 
     #[test]
     fn test_parse_ldf_node_attributes() {
@@ -318,33 +558,250 @@ mod tests {
         assert_eq!(node_attributes[0].node_name, "Slave1");
         assert_eq!(node_attributes[0].lin_protocol, "2.1");
         assert_eq!(node_attributes[0].configured_nad, 0xB);
-        assert_eq!(node_attributes[0].initial_nad, 0xB);
+        assert_eq!(node_attributes[0].initial_nad, Some(0xB));
         assert_eq!(node_attributes[0].supplier_id, 0x123);
         assert_eq!(node_attributes[0].function_id, 0x4567);
-        assert_eq!(node_attributes[0].variant, 8);
-        assert_eq!(node_attributes[0].response_error, "Signal1");
-        assert_eq!(node_attributes[0].p2_min, "100 ms");
-        assert_eq!(node_attributes[0].st_min, "0 ms");
-        assert_eq!(node_attributes[0].n_as_timeout, "1000 ms");
-        assert_eq!(node_attributes[0].n_cr_timeout, "1000 ms");
+        assert_eq!(node_attributes[0].variant, Some(8));
+        assert_eq!(node_attributes[0].response_error.as_deref(), Some("Signal1"));
+        assert_eq!(node_attributes[0].p2_min.as_deref(), Some("100 ms"));
+        assert_eq!(node_attributes[0].st_min.as_deref(), Some("0 ms"));
+        assert_eq!(node_attributes[0].n_as_timeout.as_deref(), Some("1000 ms"));
+        assert_eq!(node_attributes[0].n_cr_timeout.as_deref(), Some("1000 ms"));
         assert_eq!(node_attributes[0].configurable_frames.len(), 2);
-        assert_eq!(node_attributes[0].configurable_frames[0], "Frame1");
-        assert_eq!(node_attributes[0].configurable_frames[1], "Frame2");
+        assert_eq!(node_attributes[0].configurable_frames[0].frame_name, "Frame1");
+        assert_eq!(node_attributes[0].configurable_frames[1].frame_name, "Frame2");
 
         assert_eq!(node_attributes[1].node_name, "Slave2");
         assert_eq!(node_attributes[1].lin_protocol, "2.1");
         assert_eq!(node_attributes[1].configured_nad, 0xC);
-        assert_eq!(node_attributes[1].initial_nad, 0xC);
+        assert_eq!(node_attributes[1].initial_nad, Some(0xC));
         assert_eq!(node_attributes[1].supplier_id, 0x124);
         assert_eq!(node_attributes[1].function_id, 0x4568);
-        assert_eq!(node_attributes[1].variant, 0x66);
-        assert_eq!(node_attributes[1].response_error, "Signal2");
-        assert_eq!(node_attributes[1].p2_min, "100 ms");
-        assert_eq!(node_attributes[1].st_min, "0 ms");
-        assert_eq!(node_attributes[1].n_as_timeout, "1000 ms");
-        assert_eq!(node_attributes[1].n_cr_timeout, "1000 ms");
+        assert_eq!(node_attributes[1].variant, Some(0x66));
+        assert_eq!(node_attributes[1].response_error.as_deref(), Some("Signal2"));
+        assert_eq!(node_attributes[1].p2_min.as_deref(), Some("100 ms"));
+        assert_eq!(node_attributes[1].st_min.as_deref(), Some("0 ms"));
+        assert_eq!(node_attributes[1].n_as_timeout.as_deref(), Some("1000 ms"));
+        assert_eq!(node_attributes[1].n_cr_timeout.as_deref(), Some("1000 ms"));
         assert_eq!(node_attributes[1].configurable_frames.len(), 2);
-        assert_eq!(node_attributes[1].configurable_frames[0], "Frame1");
-        assert_eq!(node_attributes[1].configurable_frames[1], "Frame2");
+        assert_eq!(node_attributes[1].configurable_frames[0].frame_name, "Frame1");
+        assert_eq!(node_attributes[1].configurable_frames[1].frame_name, "Frame2");
+    }
+
+    #[test]
+    fn test_parse_ldf_node_attributes_any_order_and_optional() {
+        let input = r#"
+            Node_attributes {
+                Slave1 {
+                    product_id = 0x1E, 0x0001 ;
+                    configured_NAD = 12 ;
+                    LIN_protocol = "2.2" ;
+                    fault_state_signals = Signal2, Signal3 ;
+                    response_tolerance = 38.5 % ;
+                    configurable_frames {
+                        Frame1 ;
+                    }
+                    response_error = Signal1 ;
+                    P2_min = 12.5 ms ;
+                }
+                Slave2 {
+                    LIN_protocol = "2.1" ;
+                    configured_NAD = 0x0D ;
+                    product_id = 0x1E, 0x0002, 1 ;
+                }
+            }
+        "#;
+
+        let (_, node_attributes) = parse_ldf_node_attributes(input).unwrap();
+        assert_eq!(node_attributes.len(), 2);
+
+        assert_eq!(node_attributes[0].node_name, "Slave1");
+        assert_eq!(node_attributes[0].lin_protocol, "2.2");
+        assert_eq!(node_attributes[0].configured_nad, 12);
+        assert_eq!(node_attributes[0].initial_nad, None);
+        assert_eq!(node_attributes[0].supplier_id, 0x1E);
+        assert_eq!(node_attributes[0].function_id, 0x0001);
+        assert_eq!(node_attributes[0].variant, None);
+        assert_eq!(node_attributes[0].response_error.as_deref(), Some("Signal1"));
+        assert_eq!(node_attributes[0].fault_state_signals, vec!["Signal2", "Signal3"]);
+        assert_eq!(node_attributes[0].p2_min.as_deref(), Some("12.5 ms"));
+        assert_eq!(node_attributes[0].st_min, None);
+        assert_eq!(node_attributes[0].n_as_timeout, None);
+        assert_eq!(node_attributes[0].n_cr_timeout, None);
+        assert_eq!(node_attributes[0].response_tolerance, Some(38.5));
+        assert_eq!(node_attributes[0].configurable_frames.len(), 1);
+        assert_eq!(node_attributes[0].configurable_frames[0].frame_name, "Frame1");
+        assert_eq!(node_attributes[0].configurable_frames[0].message_id, None);
+
+        assert_eq!(node_attributes[1].node_name, "Slave2");
+        assert_eq!(node_attributes[1].configured_nad, 0x0D);
+        assert_eq!(node_attributes[1].variant, Some(1));
+        assert_eq!(node_attributes[1].response_error, None);
+        assert!(node_attributes[1].fault_state_signals.is_empty());
+        assert!(node_attributes[1].configurable_frames.is_empty());
+    }
+
+    #[test]
+    fn test_parse_ldf_node_attributes_missing_required() {
+        let input = r#"
+            Node_attributes {
+                Slave1 {
+                    LIN_protocol = "2.1" ;
+                    product_id = 0x1E, 0x0001 ;
+                }
+            }
+        "#;
+
+        let error = parse_ldf_node_attributes(input).err().unwrap();
+        let error = LdfError::from_parse_error(input, "Node_attributes", error);
+        assert_eq!(error.expected.as_deref(), Some("configured_NAD"));
+        assert_eq!(error.message, "expected `configured_NAD`");
+        assert_eq!(&input[error.offset..error.offset + 6], "Slave1");
+    }
+
+    #[test]
+    fn test_parse_ldf_node_attributes_unknown_and_duplicate() {
+        let input = r#"
+            Node_attributes {
+                Slave1 {
+                    LIN_protocol = "2.1" ;
+                    Supplier_setting = "a b" , 3 ;
+                    configured_NAD = 0x0B ;
+                    product_id = 0x1E, 0x0001 ;
+                    Supplier_setting = 4 ;
+                }
+            }
+        "#;
+
+        // Unknown attributes are kept with their raw value and may repeat
+        let (_, node_attributes) = parse_ldf_node_attributes(input).unwrap();
+        let unknown_attributes: Vec<(&str, &str)> = node_attributes[0]
+            .unknown_attributes
+            .iter()
+            .map(|attribute| (attribute.name.as_str(), attribute.value.as_str()))
+            .collect();
+        assert_eq!(
+            unknown_attributes,
+            vec![("Supplier_setting", "\"a b\" , 3"), ("Supplier_setting", "4")]
+        );
+        assert_eq!(node_attributes[0].configured_nad, 0x0B);
+
+        // Known attributes may only be given once
+        let duplicate = input.replace("Supplier_setting = 4", "configured_NAD = 0x0C");
+        let error = parse_ldf_node_attributes(&duplicate).err().unwrap();
+        let error = LdfError::from_parse_error(&duplicate, "Node_attributes", error);
+        assert_eq!(error.message, "duplicate attribute");
+        assert_eq!(error.snippet.trim(), "configured_NAD = 0x0C ;");
+        assert_eq!(
+            error.hint().as_deref(),
+            Some("each attribute of a node may only be given once")
+        );
+    }
+
+    #[test]
+    fn test_parse_ldf_node_attributes_malformed_times() {
+        let input = r#"
+            Node_attributes {
+                Slave1 {
+                    LIN_protocol = "2.1" ;
+                    configured_NAD = 0x0B ;
+                    product_id = 0x1E, 0x0001 ;
+                    P2_min = 50 ms ;
+                }
+            }
+        "#;
+
+        assert!(parse_ldf_node_attributes(input).is_ok());
+        for time in ["ms", "1..5 ms", "1.2.3 ms", "-5 ms"] {
+            let input = input.replace("50 ms", time);
+            assert!(parse_ldf_node_attributes(&input).is_err(), "P2_min = {} ;", time);
+        }
+    }
+
+    #[test]
+    fn test_parse_ldf_node_attributes_empty_values() {
+        let input = r#"
+            Node_attributes {
+                Slave1 {
+                    LIN_protocol = "2.1" ;
+                    configured_NAD = 0x0B ;
+                    product_id = 0x1E, 0x0001 ;
+                    response_error = Error1 ;
+                    fault_state_signals = Signal1, /* backup */ Signal2 ;
+                    configurable_frames {
+                        Frame1 ;
+                    }
+                }
+            }
+        "#;
+
+        let (_, node_attributes) = parse_ldf_node_attributes(input).unwrap();
+        assert_eq!(node_attributes[0].fault_state_signals, vec!["Signal1", "Signal2"]);
+
+        // Identifiers can't be left empty
+        for (value, empty) in [
+            ("response_error = Error1 ;", "response_error = ;"),
+            ("Signal1, /* backup */ Signal2 ;", ";"),
+            ("Signal1, /* backup */ Signal2 ;", "Signal1, ;"),
+            ("Frame1 ;", ";"),
+        ] {
+            let input = input.replace(value, empty);
+            let error = parse_ldf_node_attributes(&input).err().unwrap();
+            let error = LdfError::from_parse_error(&input, "Node_attributes", error);
+            assert_eq!(error.message, "expected an identifier", "{}", empty);
+        }
+    }
+
+    #[test]
+    fn test_parse_ldf_node_attributes_lin20_configurable_frames() {
+        let input = r#"
+            Node_attributes {
+                Slave1 {
+                    LIN_protocol = "2.0" ;
+                    configured_NAD = 0x0B ;
+                    product_id = 0x1E, 0x0001 ;
+                    configurable_frames {
+                        Frame1 = 0x1001 ;
+                        Frame2=4098;
+                    }
+                }
+            }
+        "#;
+
+        let (_, node_attributes) = parse_ldf_node_attributes(input).unwrap();
+        let configurable_frames: Vec<(&str, Option<u16>)> = node_attributes[0]
+            .configurable_frames
+            .iter()
+            .map(|frame| (frame.frame_name.as_str(), frame.message_id))
+            .collect();
+        assert_eq!(
+            configurable_frames,
+            vec![("Frame1", Some(0x1001)), ("Frame2", Some(0x1002))]
+        );
+    }
+
+    #[test]
+    fn test_parse_ldf_node_attributes_j2602() {
+        let input = r#"
+            Node_attributes {
+                Slave1 {
+                    LIN_protocol = "J2602_1_1.0" ;
+                    configured_NAD = 0x60 ;
+                    product_id = 0x1E, 0x0001, 0 ;
+                    response_error = Signal1 ;
+                    response_tolerance = 38 % ;
+                    wakeup_time = 100 ms ;
+                    poweron_time = 150 ms ;
+                }
+            }
+        "#;
+
+        let (_, node_attributes) = parse_ldf_node_attributes(input).unwrap();
+        assert_eq!(node_attributes.len(), 1);
+        assert_eq!(node_attributes[0].lin_protocol, "J2602_1_1.0");
+        assert_eq!(node_attributes[0].configured_nad, 0x60);
+        assert_eq!(node_attributes[0].response_tolerance, Some(38.0));
+        assert_eq!(node_attributes[0].wakeup_time.as_deref(), Some("100 ms"));
+        assert_eq!(node_attributes[0].poweron_time.as_deref(), Some("150 ms"));
     }
 }
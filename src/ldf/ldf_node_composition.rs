@@ -0,0 +1,267 @@
+use crate::ldf::ldf_comment::skip_whitespace;
+use crate::ldf::ldf_error::{tag, IResult};
+use crate::ldf::ldf_identifier::{parse_identifier, parse_identifier_list};
+use crate::ldf::ldf_span::{LdfNamedSpan, LdfSpan, LineIndex, ResolveSpans};
+
+/// `Node_composition` section of a LIN Description File (LDF)
+/// ```text
+/// Node_composition {
+///   configuration Cfg1 {
+///     Composite1 { Slave1, Slave2 } ;
+///     Composite2 { Slave3 } ;
+///   }
+/// }
+/// ```
+/// ---
+/// Configuration in the `Node_composition` section of a LIN Description File (LDF)
+/// ```text
+/// configuration <configuration_name> {
+///   <composite_node> { <logical_node> [, <logical_node>] } ;
+/// }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub struct LdfNodeComposition {
+    /// Configuration name
+    pub configuration_name: String,
+
+    /// Composite (physical) nodes of the configuration
+    pub composite_nodes: Vec<LdfCompositeNode>,
+
+    /// Location of the configuration in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
+}
+
+/// Composite node in a `Node_composition` configuration of a LIN Description File (LDF)
+/// ```text
+/// Composite1 { Slave1, Slave2 } ;
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub struct LdfCompositeNode {
+    /// Composite node name, i.e. the physical node
+    pub name: String,
+
+    /// Logical nodes (from the `Nodes` section) that make up the composite node
+    pub logical_nodes: Vec<String>,
+
+    /// Location of the composite node in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
+
+    /// Identifiers referenced by the composite node (logical nodes), with their location in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub references: Vec<LdfNamedSpan>,
+}
+
+impl LdfNodeComposition {
+    /// Composite node that contains the logical node `logical_node` in this configuration, if any
+    pub fn composite_node_of(&self, logical_node: &str) -> Option<&LdfCompositeNode> {
+        self.composite_nodes
+            .iter()
+            .find(|composite_node| composite_node.logical_nodes.iter().any(|node| node == logical_node))
+    }
+}
+
+impl ResolveSpans for LdfNodeComposition {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+        self.composite_nodes.resolve_spans(index);
+    }
+}
+
+impl ResolveSpans for LdfCompositeNode {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+        self.references.resolve_spans(index);
+    }
+}
+
+/*
+Node_composition {
+  configuration Cfg1 {
+    Composite1 { Slave1, Slave2 } ;
+    Composite2 { Slave3 } ;
+  }
+}
+*/
+
+pub fn parse_ldf_node_composition(s: &str) -> IResult<&str, Vec<LdfNodeComposition>> {
+    // `Node_composition {` or `Node_composition{` or ...
+    // - May be any number of spaces before and after the "Node_composition" tag
+    // - May be any number of spaces before and after the opening curly brace
+    let (s, _) = skip_whitespace(s)?;
+
+    // Assume that if the section label is not present, then the section is not present.
+    if !s.starts_with("Node_composition") {
+        return Ok((s, Vec::new()));
+    }
+
+    let (s, _) = tag("Node_composition")(s)?;
+    let (s, _) = skip_whitespace(s)?;
+    let (s, _) = tag("{")(s)?;
+    let (s, _) = skip_whitespace(s)?;
+
+    let mut node_compositions = Vec::new();
+    let mut remaining = s;
+
+    while !remaining.starts_with('}') {
+        // `configuration Cfg1 {` or `configuration Cfg1{` or ...
+        // - May be any number of spaces before and after the "configuration" tag
+        // - May be any number of spaces before and after the configuration name
+        // - May be any number of spaces before and after the opening curly brace
+        let entry = remaining;
+        let (s, _) = tag("configuration")(remaining)?;
+        let (s, _) = skip_whitespace(s)?;
+        let (s, configuration_name) = parse_identifier(s)?;
+        let (s, _) = skip_whitespace(s)?;
+        let (s, _) = tag("{")(s)?;
+        let (s, _) = skip_whitespace(s)?;
+
+        let mut composite_nodes = Vec::new();
+        remaining = s;
+
+        while !remaining.starts_with('}') {
+            // `Composite1 { Slave1, Slave2 } ;` or `Composite1{Slave1,Slave2}` or ...
+            // - May be any number of spaces before and after the composite node name
+            // - May be any number of spaces before and after the curly braces
+            // - May be any number of spaces before and after each logical node name
+            // - The semicolon after the closing curly brace is optional
+            let composite_entry = remaining;
+            let (s, name) = parse_identifier(remaining)?;
+            let (s, _) = skip_whitespace(s)?;
+            let (s, _) = tag("{")(s)?;
+            let (s, logical_nodes) = parse_identifier_list(s)?;
+            let (after_brace, _) = tag("}")(s)?;
+            let (s, _) = skip_whitespace(after_brace)?;
+            let s = match s.strip_prefix(';') {
+                Some(s) => s,
+                None => after_brace,
+            };
+            let span = LdfSpan::new(composite_entry, s);
+            let (s, _) = skip_whitespace(s)?;
+
+            let references = logical_nodes
+                .iter()
+                .map(|node| LdfNamedSpan::of(composite_entry, node))
+                .collect();
+            let logical_nodes = logical_nodes.iter().map(|node| node.to_string()).collect();
+
+            composite_nodes.push(LdfCompositeNode {
+                name: name.to_string(),
+                logical_nodes,
+                span,
+                references,
+            });
+
+            remaining = s;
+        }
+
+        let (s, _) = tag("}")(remaining)?;
+        let span = LdfSpan::new(entry, s);
+        let (s, _) = skip_whitespace(s)?;
+
+        node_compositions.push(LdfNodeComposition {
+            configuration_name: configuration_name.to_string(),
+            composite_nodes,
+            span,
+        });
+
+        remaining = s;
+    }
+
+    let (remaining, _) = tag("}")(remaining)?;
+
+    Ok((remaining, node_compositions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ldf_node_composition() {
+        let input = r#"
+            Node_composition {
+                configuration Cfg1 {
+                    Composite1 { Slave1, Slave2 } ;
+                    Composite2{Slave3}
+                }
+                configuration Cfg2 {
+                    Composite3 { Slave1 , Slave2, Slave3 } ;
+                }
+            }
+        "#;
+
+        let (_, node_compositions) = parse_ldf_node_composition(input).unwrap();
+        assert_eq!(node_compositions.len(), 2);
+
+        assert_eq!(node_compositions[0].configuration_name, "Cfg1");
+        assert_eq!(node_compositions[0].composite_nodes.len(), 2);
+        assert_eq!(node_compositions[0].composite_nodes[0].name, "Composite1");
+        assert_eq!(
+            node_compositions[0].composite_nodes[0].logical_nodes,
+            vec!["Slave1", "Slave2"]
+        );
+        assert_eq!(node_compositions[0].composite_nodes[1].name, "Composite2");
+        assert_eq!(node_compositions[0].composite_nodes[1].logical_nodes, vec!["Slave3"]);
+
+        assert_eq!(node_compositions[1].configuration_name, "Cfg2");
+        assert_eq!(node_compositions[1].composite_nodes.len(), 1);
+        assert_eq!(
+            node_compositions[1].composite_nodes[0].logical_nodes,
+            vec!["Slave1", "Slave2", "Slave3"]
+        );
+
+        assert_eq!(
+            node_compositions[0]
+                .composite_node_of("Slave3")
+                .map(|node| node.name.as_str()),
+            Some("Composite2")
+        );
+        assert_eq!(
+            node_compositions[1]
+                .composite_node_of("Slave3")
+                .map(|node| node.name.as_str()),
+            Some("Composite3")
+        );
+        assert!(node_compositions[0].composite_node_of("Slave4").is_none());
+    }
+
+    #[test]
+    fn test_parse_ldf_node_composition_comments() {
+        let input = r#"
+            Node_composition {
+                configuration Cfg1 {
+                    Composite1 { Slave1, // front
+                        Slave2 /* rear */ } ;
+                }
+            }
+        "#;
+
+        let (_, node_compositions) = parse_ldf_node_composition(input).unwrap();
+        let composite_node = &node_compositions[0].composite_nodes[0];
+        assert_eq!(composite_node.logical_nodes, vec!["Slave1", "Slave2"]);
+        let references: Vec<&str> = composite_node
+            .references
+            .iter()
+            .map(|reference| reference.name.as_str())
+            .collect();
+        assert_eq!(references, vec!["Slave1", "Slave2"]);
+
+        let input = r#"
+            Node_composition {
+                configuration Cfg1 {
+                    Composite1 { } ;
+                }
+            }
+        "#;
+        assert!(parse_ldf_node_composition(input).is_err());
+    }
+}
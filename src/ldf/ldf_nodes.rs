@@ -1,8 +1,8 @@
 use crate::ldf::ldf_comment::skip_whitespace;
-use nom::{
-    bytes::complete::{tag, take_until, take_while},
-    IResult,
-};
+use crate::ldf::ldf_error::{tag, take_until, IResult};
+use crate::ldf::ldf_number::{parse_integer, parse_milliseconds, parse_milliseconds_value, parse_real};
+use crate::ldf::ldf_span::{LdfSpan, LineIndex, ResolveSpans};
+use nom::bytes::complete::take_while;
 
 /// `Nodes` section of a LIN Description File (LDF) for LIN 2.1
 /// ```text
@@ -11,6 +11,11 @@ use nom::{
 ///   Slaves: Slave1, Slave2, Slave3 ;
 /// }
 /// ```
+/// SAE J2602 files extend the master definition with the maximum header length and the response tolerance:
+/// ```text
+/// Master: Master, 5 ms, 0.1 ms, 48 bits, 40 % ;
+/// ```
+#[derive(Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
@@ -20,6 +25,7 @@ pub struct LdfNodes {
 }
 
 /// Master node in the `Nodes` section of a LIN Description File (LDF) for LIN 2.1
+#[derive(Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
@@ -34,11 +40,27 @@ pub struct MasterNode {
     // Defined in section (2.4.1 TIME DEFINITIONS) of the LIN 2.1 specification.
     pub time_base: String,
 
+    // Same as `time_base`, as a number of milliseconds (e.g. 5.0)
+    pub time_base_ms: f32,
+
     // `<jitter> ms`
     // Specifies the differences between the maximum and minimum delay from time base tick
     // to the header sending start point (falling edge of break field).
     // Defined in section (2.4.1 TIME DEFINITIONS) of the LIN 2.1 specification.
     pub jitter: String,
+
+    // `<max_header_length> bits` (SAE J2602 only)
+    // Maximum length of the frame header (break, sync and protected identifier) in bits.
+    pub max_header_length: Option<u8>,
+
+    // `<response_tolerance> %` (SAE J2602 only)
+    // Tolerance of the response length, in percent of the nominal response length.
+    pub response_tolerance: Option<f32>,
+
+    // Location of the master definition in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
 }
 
 /// Slave node in the `Nodes` section of a LIN Description File (LDF) for LIN 2.1
@@ -48,6 +70,30 @@ pub struct MasterNode {
 pub struct Node {
     // All identifiers must be unique within the LDF file.
     pub name: String,
+
+    // Location of the node name in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
+}
+
+impl ResolveSpans for LdfNodes {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.master.resolve_spans(index);
+        self.slaves.resolve_spans(index);
+    }
+}
+
+impl ResolveSpans for MasterNode {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+    }
+}
+
+impl ResolveSpans for Node {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+    }
 }
 
 /*
@@ -76,6 +122,7 @@ pub fn parse_ldf_nodes(s: &str) -> IResult<&str, LdfNodes> {
     // - May be any number of spaces before and after the time value
     // - May be any number of spaces before and after the semicolon
     let (s, _) = skip_whitespace(s)?;
+    let master_entry = s;
     let (s, _) = tag("Master")(s)?;
     let (s, _) = skip_whitespace(s)?;
     let (s, _) = tag(":")(s)?;
@@ -84,22 +131,45 @@ pub fn parse_ldf_nodes(s: &str) -> IResult<&str, LdfNodes> {
     let (s, _) = skip_whitespace(s)?;
     let (s, _) = tag(",")(s)?;
     let (s, _) = skip_whitespace(s)?;
-    let (s, time_base) = take_while(|c: char| c.is_numeric() || c == '.')(s)?;
-    let (s, _) = skip_whitespace(s)?;
-    let (s, _) = tag("ms")(s)?;
+    let (_, time_base_ms) = parse_milliseconds_value(s)?;
+    let (s, time_base) = parse_milliseconds(s)?;
     let (s, _) = skip_whitespace(s)?;
     let (s, _) = tag(",")(s)?;
     let (s, _) = skip_whitespace(s)?;
-    let (s, jitter) = take_while(|c: char| c.is_numeric() || c == '.')(s)?;
-    let (s, _) = skip_whitespace(s)?;
-    let (s, _) = tag("ms")(s)?;
+    let (s, jitter) = parse_milliseconds(s)?;
     let (s, _) = skip_whitespace(s)?;
+
+    // `, 48 bits, 40 %` (SAE J2602 only)
+    // - May be any number of spaces before and after the comma
+    // - May be any number of spaces before and after the max header length and the "bits" tag
+    // - May be any number of spaces before and after the response tolerance and the "%" tag
+    let (s, max_header_length, response_tolerance) = match s.strip_prefix(',') {
+        Some(s) => {
+            let (s, _) = skip_whitespace(s)?;
+            let (s, max_header_length) = parse_integer(s)?;
+            let (s, _) = skip_whitespace(s)?;
+            let (s, _) = tag("bits")(s)?;
+            let (s, _) = skip_whitespace(s)?;
+            let (s, _) = tag(",")(s)?;
+            let (s, _) = skip_whitespace(s)?;
+            let (s, response_tolerance) = parse_real(s)?;
+            let (s, _) = skip_whitespace(s)?;
+            let (s, _) = tag("%")(s)?;
+            let (s, _) = skip_whitespace(s)?;
+            (s, Some(max_header_length), Some(response_tolerance as f32))
+        }
+        None => (s, None, None),
+    };
     let (s, _) = tag(";")(s)?;
 
     let master = MasterNode {
         name: master_node_name.to_string(),
-        time_base: time_base.to_string() + " ms",
-        jitter: jitter.to_string() + " ms",
+        time_base,
+        time_base_ms: time_base_ms as f32,
+        jitter,
+        max_header_length,
+        response_tolerance,
+        span: LdfSpan::new(master_entry, s),
     };
 
     // `Slaves: Slave1, Slave2, Slave3 ;` or `Slaves: Slave1, Slave2, Slave3;` or ...
@@ -114,6 +184,7 @@ pub fn parse_ldf_nodes(s: &str) -> IResult<&str, LdfNodes> {
     let (s, _) = skip_whitespace(s)?;
     let (s, _) = tag(":")(s)?;
     let (s, _) = skip_whitespace(s)?;
+    let slaves_entry = s;
     let (s, slaves) = take_until(";")(s)?;
     let (s, _) = tag(";")(s)?;
 
@@ -121,6 +192,7 @@ pub fn parse_ldf_nodes(s: &str) -> IResult<&str, LdfNodes> {
         .split(",")
         .map(|slave| Node {
             name: slave.trim().to_string(),
+            span: LdfSpan::of(slaves_entry, slave.trim()),
         })
         .collect();
 
@@ -151,9 +223,29 @@ mod tests {
         assert_eq!(header.master.name, "Master");
         assert_eq!(header.master.time_base, "5 ms");
         assert_eq!(header.master.jitter, "0.1 ms");
+        assert_eq!(header.master.max_header_length, None);
+        assert_eq!(header.master.response_tolerance, None);
         assert_eq!(header.slaves.len(), 3);
         assert_eq!(header.slaves[0].name, "Slave1");
         assert_eq!(header.slaves[1].name, "Slave2");
         assert_eq!(header.slaves[2].name, "Slave3");
     }
+
+    #[test]
+    fn test_parse_j2602() {
+        let s = r#"
+            Nodes {
+                Master: CEM, 5 ms, 0.1 ms, 48 bits, 40 % ;
+                Slaves: Slave1 ;
+            }
+        "#;
+
+        let (_, nodes) = parse_ldf_nodes(s).unwrap();
+        assert_eq!(nodes.master.name, "CEM");
+        assert_eq!(nodes.master.time_base, "5 ms");
+        assert_eq!(nodes.master.jitter, "0.1 ms");
+        assert_eq!(nodes.master.max_header_length, Some(48));
+        assert_eq!(nodes.master.response_tolerance, Some(40.0));
+        assert_eq!(nodes.slaves.len(), 1);
+    }
 }
@@ -0,0 +1,142 @@
+use crate::ldf::ldf_comment::skip_whitespace;
+use crate::ldf::ldf_error::{tag, IResult, LdfParseError};
+use nom::{
+    branch::alt,
+    bytes::complete::tag_no_case,
+    character::complete::{digit1, hex_digit1},
+    combinator::recognize,
+    error::ErrorKind,
+    number::complete::recognize_float,
+    sequence::pair,
+};
+
+/// Integer in decimal (`12`) or hexadecimal (`0x0C`) notation.
+///
+/// Fails (instead of panicking) if the value does not fit into the requested integer type.
+pub fn parse_integer<T: TryFrom<u64>>(s: &str) -> IResult<&str, T> {
+    let (remaining, digits) = alt((recognize(pair(tag_no_case("0x"), hex_digit1)), digit1))(s)?;
+    let value = match digits.get(..2) {
+        Some("0x") | Some("0X") => u64::from_str_radix(&digits[2..], 16).ok(),
+        _ => digits.parse::<u64>().ok(),
+    };
+    match value.and_then(|value| T::try_from(value).ok()) {
+        Some(value) => Ok((remaining, value)),
+        None => Err(nom::Err::Error(LdfParseError::new(s, ErrorKind::Digit))),
+    }
+}
+
+/// Same as [`parse_integer`], but for an already isolated token (e.g. one item of a comma separated list).
+pub fn parse_integer_token<T: TryFrom<u64>>(token: &str) -> IResult<&str, T> {
+    let (remaining, value) = parse_integer(token.trim())?;
+    if !remaining.is_empty() {
+        return Err(nom::Err::Error(LdfParseError::new(remaining, ErrorKind::Eof)));
+    }
+    Ok((remaining, value))
+}
+
+/// Integer in decimal (`-12`) or hexadecimal (`0x0C`) notation that may be negative.
+pub fn parse_signed_integer<T: TryFrom<i64>>(s: &str) -> IResult<&str, T> {
+    let (remaining, value) = match s.strip_prefix('-') {
+        Some(unsigned) => {
+            let (remaining, value) = parse_integer::<i64>(unsigned)?;
+            (remaining, -value)
+        }
+        None => parse_integer::<i64>(s)?,
+    };
+    match T::try_from(value) {
+        Ok(value) => Ok((remaining, value)),
+        Err(_) => Err(nom::Err::Error(LdfParseError::new(s, ErrorKind::Digit))),
+    }
+}
+
+/// Real number with optional sign, fraction and exponent (`10`, `-40`, `0.5`, `.5`, `1.5E-05`).
+pub fn parse_real(s: &str) -> IResult<&str, f64> {
+    let (remaining, real) = recognize_float(s)?;
+    match real.parse::<f64>() {
+        Ok(value) => Ok((remaining, value)),
+        Err(_) => Err(nom::Err::Error(LdfParseError::new(s, ErrorKind::Float))),
+    }
+}
+
+/// Time in milliseconds (`100 ms`, `12.5 ms`), returned as written (e.g. `12.5 ms`).
+///
+/// Fails if the number is malformed or negative.
+pub fn parse_milliseconds(s: &str) -> IResult<&str, String> {
+    let (remaining, _) = parse_milliseconds_value(s)?;
+    let (_, number) = recognize_float(s)?;
+
+    Ok((remaining, number.trim_start_matches('+').to_string() + " ms"))
+}
+
+/// Same as [`parse_milliseconds`], but returns the number of milliseconds (e.g. `12.5`).
+pub fn parse_milliseconds_value(s: &str) -> IResult<&str, f64> {
+    let (remaining, value) = parse_real(s)?;
+    if !value.is_finite() || value < 0.0 {
+        return Err(nom::Err::Error(LdfParseError::new(s, ErrorKind::MapRes)));
+    }
+    let (remaining, _) = skip_whitespace(remaining)?;
+    let (remaining, _) = tag("ms")(remaining)?;
+
+    Ok((remaining, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_integer() {
+        assert_eq!(parse_integer::<u8>("12 ;"), Ok((" ;", 12)));
+        assert_eq!(parse_integer::<u8>("0x0C ;"), Ok((" ;", 0x0C)));
+        assert_eq!(parse_integer::<u16>("0XFFFE,"), Ok((",", 0xFFFE)));
+        assert!(parse_integer::<u8>("0x1FF").is_err());
+        assert!(parse_integer::<u8>("256").is_err());
+        assert!(parse_integer::<u8>("").is_err());
+        assert!(parse_integer::<u8>("Frame1").is_err());
+    }
+
+    #[test]
+    fn test_parse_integer_token() {
+        assert_eq!(parse_integer_token::<u8>(" 0x3C "), Ok(("", 0x3C)));
+        assert!(parse_integer_token::<u8>("0x3C 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_signed_integer() {
+        assert_eq!(parse_signed_integer::<i32>("-40,"), Ok((",", -40)));
+        assert_eq!(parse_signed_integer::<i32>("0xFFFE,"), Ok((",", 0xFFFE)));
+        assert!(parse_signed_integer::<i8>("-0x81").is_err());
+    }
+
+    #[test]
+    fn test_parse_real() {
+        assert_eq!(parse_real("10,"), Ok((",", 10.0)));
+        assert_eq!(parse_real("-40 ;"), Ok((" ;", -40.0)));
+        assert_eq!(parse_real(".5,"), Ok((",", 0.5)));
+        assert_eq!(parse_real("+2.,"), Ok((",", 2.0)));
+        assert_eq!(parse_real("1E-05,"), Ok((",", 1E-05)));
+        assert_eq!(parse_real("1.5e+3,"), Ok((",", 1500.0)));
+        assert!(parse_real("x").is_err());
+    }
+
+    #[test]
+    fn test_parse_milliseconds() {
+        assert_eq!(parse_milliseconds("100 ms ;"), Ok((" ;", "100 ms".to_string())));
+        assert_eq!(parse_milliseconds("12.5ms,"), Ok((",", "12.5 ms".to_string())));
+        assert!(parse_milliseconds("ms ;").is_err());
+        assert!(parse_milliseconds("1..5 ms ;").is_err());
+        assert!(parse_milliseconds("1.2.3 ms ;").is_err());
+        assert_eq!(
+            parse_milliseconds("-5 ms ;").err().unwrap(),
+            nom::Err::Error(LdfParseError::new("-5 ms ;", ErrorKind::MapRes))
+        );
+    }
+
+    #[test]
+    fn test_parse_milliseconds_value() {
+        assert_eq!(parse_milliseconds_value("+12.5 ms ;"), Ok((" ;", 12.5)));
+        assert!(parse_milliseconds_value("-10 ms ;").is_err());
+        assert!(parse_milliseconds_value("1E999 ms ;").is_err());
+        assert!(parse_milliseconds_value("10 ;").is_err());
+    }
+}
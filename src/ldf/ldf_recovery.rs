@@ -0,0 +1,269 @@
+use crate::ldf::ldf_comment::skip_whitespace;
+use crate::ldf::ldf_error::{IResult, LdfError};
+use crate::ldf::ldf_span::{LdfNamedSpan, LdfSpan};
+
+/// Position in a LIN Description File (LDF) that is parsed section by section, and the errors found so far.
+///
+/// When a section fails to parse, the broken entry (up to the next `;` or the matching `}`) is blanked out
+/// and the section is parsed again without it. Blanking replaces the entry with spaces, so that the byte
+/// offsets of all errors still point into the original file.
+pub(crate) struct Recovery<'a> {
+    source: &'a str,
+    text: String,
+    offset: usize,
+    pub errors: Vec<LdfError>,
+    /// Location of each section (including skipped ones), relative to the end of the file like [`LdfSpan::new`]
+    pub section_spans: Vec<LdfNamedSpan>,
+}
+
+impl<'a> Recovery<'a> {
+    pub fn new(source: &'a str) -> Recovery<'a> {
+        Recovery {
+            source,
+            text: source.to_string(),
+            offset: 0,
+            errors: Vec::new(),
+            section_spans: Vec::new(),
+        }
+    }
+
+    /// Text that is left to parse
+    pub fn remaining(&self) -> &str {
+        &self.text[self.offset..]
+    }
+
+    /// Byte offset of the current position in the file
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Skips whitespace and comments at the current position
+    pub fn skip_whitespace(&mut self) {
+        let remaining = self.remaining();
+        let (remaining, _) = skip_whitespace(remaining).unwrap_or((remaining, ""));
+        self.offset = self.text.len() - remaining.len();
+    }
+
+    /// Records an error, unless it is the same as the last one (which happens when recovering didn't help)
+    pub fn error(&mut self, error: LdfError) {
+        let is_repeated = self
+            .errors
+            .last()
+            .is_some_and(|last| last.offset == error.offset && last.message == error.message);
+        if !is_repeated {
+            self.errors.push(error);
+        }
+    }
+
+    /// Records an error at `offset` bytes into the file
+    pub fn error_at(&mut self, offset: usize, section: &str, expected: Option<&str>, message: String) {
+        let error = LdfError::new(self.source, offset, section, expected, message);
+        self.error(error);
+    }
+
+    /// Takes the snippet of an error in the text from the original file, which may have blanked out entries
+    fn in_source(&self, error: LdfError) -> LdfError {
+        LdfError {
+            kind: error.kind,
+            ..LdfError::new(
+                self.source,
+                error.offset,
+                &error.section,
+                error.expected.as_deref(),
+                error.message,
+            )
+        }
+    }
+
+    /// Parses the statements at the start of the file (e.g. the header).
+    /// If the parser fails, the error is recorded and all statements up to the first section are skipped.
+    pub fn parse_statements<T>(&mut self, section: &str, parser: impl Fn(&str) -> IResult<&str, T>) -> Option<T> {
+        self.skip_whitespace();
+        let start = self.offset;
+        let value = self.parse_statements_at(section, parser);
+        self.record_span(section, start);
+        value
+    }
+
+    fn parse_statements_at<T>(&mut self, section: &str, parser: impl Fn(&str) -> IResult<&str, T>) -> Option<T> {
+        let error = match parser(self.remaining()) {
+            Ok((remaining, value)) => {
+                self.offset = self.text.len() - remaining.len();
+                return Some(value);
+            }
+            Err(error) => LdfError::from_parse_error(&self.text, section, error),
+        };
+        let error = self.in_source(error);
+        self.offset = error.offset;
+        self.error(error);
+
+        // Statements end with a `;` and have no curly braces
+        loop {
+            let remaining = self.remaining();
+            match remaining.find([';', '{', '}']) {
+                Some(i) if remaining[i..].starts_with(';') => self.offset += i + 1,
+                _ => return None,
+            }
+        }
+    }
+
+    /// Parses the section at the current position. If the parser fails, the error is recorded and the
+    /// broken entry is blanked out before trying again. If that doesn't help either, the whole section
+    /// is skipped and `None` is returned.
+    pub fn parse_section<T>(&mut self, section: &str, parser: impl Fn(&str) -> IResult<&str, T>) -> Option<T> {
+        let start = self.offset;
+        let value = self.parse_section_at(start, section, parser);
+        self.record_span(section, start);
+        value
+    }
+
+    fn parse_section_at<T>(
+        &mut self,
+        start: usize,
+        section: &str,
+        parser: impl Fn(&str) -> IResult<&str, T>,
+    ) -> Option<T> {
+        let body_start = self.text[start..].find('{').map(|i| start + i + 1);
+
+        loop {
+            let error = match parser(&self.text[start..]) {
+                Ok((remaining, value)) => {
+                    self.offset = self.text.len() - remaining.len();
+                    return Some(value);
+                }
+                Err(error) => LdfError::from_parse_error(&self.text, section, error),
+            };
+            let error = self.in_source(error);
+            let error_offset = error.offset;
+            self.error(error);
+
+            let blanked = match body_start {
+                // Errors before the opening curly brace cannot be recovered from
+                Some(body_start) if error_offset >= body_start => self.blank_entry(body_start, error_offset),
+                _ => false,
+            };
+            if !blanked {
+                self.skip_section(start);
+                return None;
+            }
+        }
+    }
+
+    /// Records the span of the section from `start` to the current position
+    fn record_span(&mut self, section: &str, start: usize) {
+        self.section_spans.push(LdfNamedSpan {
+            name: section.to_string(),
+            span: LdfSpan::new(&self.text[start..], self.remaining()),
+        });
+    }
+
+    /// Skips the section (or stray statement) starting at `start`, up to the next `;` or the matching `}`
+    pub fn skip_section(&mut self, start: usize) {
+        let end = entry_end(&self.text, start);
+        // A stray `}` is skipped as well, to make progress
+        self.offset = match end == start && start < self.text.len() {
+            true => start + 1,
+            false => end,
+        };
+    }
+
+    /// Blanks out the entry around `error_offset`, without going before `lower` (the start of the section body).
+    /// Returns `false` if there is nothing left to blank out.
+    fn blank_entry(&mut self, lower: usize, error_offset: usize) -> bool {
+        // The entry starts after the previous `;`, `{` or `}`
+        let entry_start = self.text[lower..error_offset]
+            .rfind([';', '{', '}'])
+            .map_or(lower, |i| lower + i + 1);
+        let entry_end = entry_end(&self.text, error_offset);
+
+        let entry = &self.text[entry_start..entry_end];
+        if entry.trim().is_empty() {
+            return false;
+        }
+
+        // Keep the line breaks, so that line numbers stay the same
+        let blanked: String = entry
+            .chars()
+            .map(|c| match c {
+                '\n' => "\n".to_string(),
+                c => " ".repeat(c.len_utf8()),
+            })
+            .collect();
+        self.text.replace_range(entry_start..entry_end, &blanked);
+        true
+    }
+}
+
+/// End of the entry at `from`: just after the next `;` or the matching `}` of a block (and a `;` after it).
+/// Stops before a `}` that closes the enclosing block.
+fn entry_end(text: &str, from: usize) -> usize {
+    let mut depth = 0;
+    for (i, c) in text[from..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => return from + i,
+            '}' if depth == 1 => {
+                let end = from + i + 1;
+                let rest = text[end..].trim_start();
+                return match rest.strip_prefix(';') {
+                    Some(after) => text.len() - after.len(),
+                    None => end,
+                };
+            }
+            '}' => depth -= 1,
+            ';' if depth == 0 => return from + i + 1,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_end() {
+        let text = "Signal1, 0 ; Signal2, 8 ; }";
+        assert_eq!(entry_end(text, 0), text.find(';').unwrap() + 1);
+
+        let text = "Frame1: 0x1FF, Master, 8 { Signal1, 0 ; } ; Frame2";
+        assert_eq!(&text[entry_end(text, 0)..], " Frame2");
+
+        let text = "Signal1, 0 }";
+        assert_eq!(&text[entry_end(text, 0)..], "}");
+
+        assert_eq!(entry_end("Vendor {", 0), 8);
+    }
+
+    #[test]
+    fn test_recovery_blanks_broken_entry() {
+        let source = "Dynamic_frames {\n  0x38 ;\n  0x1FF ;\n  0x39 ;\n}\nrest";
+        let mut recovery = Recovery::new(source);
+        let dynamic_frames = recovery.parse_section(
+            "Dynamic_frames",
+            crate::ldf::ldf_dynamic_frames::parse_ldf_dynamic_frames,
+        );
+
+        assert_eq!(dynamic_frames, Some(vec![0x38, 0x39]));
+        assert_eq!(recovery.remaining(), "\nrest");
+        assert_eq!(recovery.errors.len(), 1);
+        assert_eq!(recovery.errors[0].line, 3);
+        assert_eq!(recovery.errors[0].snippet, "  0x1FF ;");
+    }
+
+    #[test]
+    fn test_recovery_skips_unrecoverable_section() {
+        let source = "Dynamic_frames ; 0x38 ; }\nrest";
+        let mut recovery = Recovery::new(source);
+        let dynamic_frames = recovery.parse_section(
+            "Dynamic_frames",
+            crate::ldf::ldf_dynamic_frames::parse_ldf_dynamic_frames,
+        );
+
+        assert_eq!(dynamic_frames, None);
+        assert_eq!(recovery.remaining(), " 0x38 ; }\nrest");
+        assert_eq!(recovery.errors.len(), 1);
+        assert_eq!(recovery.errors[0].expected.as_deref(), Some("{"));
+    }
+}
@@ -1,8 +1,8 @@
 use crate::ldf::ldf_comment::skip_whitespace;
-use nom::{
-    bytes::complete::{tag, take_while},
-    IResult,
-};
+use crate::ldf::ldf_error::{tag, take_until, IResult, LdfParseError};
+use crate::ldf::ldf_number::{parse_integer_token, parse_milliseconds_value};
+use crate::ldf::ldf_span::{list_spans, LdfNamedSpan, LdfSpan, LineIndex, ResolveSpans};
+use nom::{bytes::complete::take_while, error::ErrorKind};
 
 /// `Schedule_tables` section of a LIN Description File (LDF)
 /// ```text
@@ -11,6 +11,11 @@ use nom::{
 ///      Frame1 delay 10 ms ;
 ///      Frame2 delay 10 ms ;
 ///   }
+///   Configuration {
+///      AssignNAD { Slave1 } delay 20 ms ;
+///      MasterReq delay 10 ms ;
+///      SlaveResp delay 10 ms ;
+///   }
 /// }
 /// ```
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -23,21 +28,149 @@ pub struct LdfScheduleTable {
 
     /// Frame delays
     pub frame_delays: Vec<LdfFrameDelay>,
+
+    /// Location of the schedule table in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
 }
 
-/// Frame delay in a schedule table in a LIN Description File (LDF).
+/// Entry (slot) in a schedule table in a LIN Description File (LDF).
 /// ```text
 /// Frame1 delay 10 ms ;
+/// AssignNAD { Slave1 } delay 20 ms ;
+/// ```
+/// Reads as:
+/// ```text
+/// <command> delay <frame_time> ms ;
 /// ```
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
 pub struct LdfFrameDelay {
-    /// Frame name
-    pub frame_name: String,
+    /// Frame or diagnostic/configuration command sent in the slot
+    pub command: LdfScheduleCommand,
 
     /// Frame delay in milliseconds
     pub frame_time: f32,
+
+    /// Location of the entry in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
+
+    /// Identifiers referenced by the entry (frame or node and frame of the command), with their location in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub references: Vec<LdfNamedSpan>,
+}
+
+/// Command of a schedule table entry in a LIN Description File (LDF).
+/// ```text
+/// <command> ::=
+///   <frame_name> |
+///   MasterReq |
+///   SlaveResp |
+///   AssignNAD { <node_name> } |
+///   ConditionalChangeNAD { <NAD>, <id>, <byte>, <mask>, <inv>, <new_NAD> } |
+///   DataDump { <node_name>, <D1>, <D2>, <D3>, <D4>, <D5> } |
+///   SaveConfiguration { <node_name> } |
+///   AssignFrameIdRange { <node_name>, <frame_index> [, <frame_PID>, <frame_PID>, <frame_PID>, <frame_PID>] } |
+///   FreeFormat { <D1>, <D2>, <D3>, <D4>, <D5>, <D6>, <D7>, <D8> } |
+///   AssignFrameId { <node_name>, <frame_name> } |
+///   UnassignFrameId { <node_name>, <frame_name> }
+/// ```
+///
+/// Serialized with a `type` field naming the variant, e.g. `{ "type": "Frame", "frame_name": "Frame1" }`.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export, tag = "type"))]
+pub enum LdfScheduleCommand {
+    /// Unconditional, sporadic or event triggered frame
+    Frame {
+        /// Frame name
+        frame_name: String,
+    },
+    /// Master request frame (0x3C)
+    MasterReq,
+    /// Slave response frame (0x3D)
+    SlaveResp,
+    /// Assign the configured NAD to the node (LIN 2.1)
+    AssignNad {
+        /// Node name
+        node_name: String,
+    },
+    /// Conditionally change the NAD of any node matching the condition (LIN 2.1)
+    ConditionalChangeNad {
+        /// NAD the request is addressed to
+        nad: u8,
+        /// Identifier of the data to read
+        id: u8,
+        /// Byte of the data to check
+        byte: u8,
+        /// Mask applied to the byte
+        mask: u8,
+        /// Value the byte is inverted with
+        inv: u8,
+        /// New NAD
+        new_nad: u8,
+    },
+    /// Supplier specific data dump to the node (LIN 2.1)
+    DataDump {
+        /// Node name
+        node_name: String,
+        /// Data bytes D1 to D5
+        data: [u8; 5],
+    },
+    /// Ask the node to save its current configuration (LIN 2.1)
+    SaveConfiguration {
+        /// Node name
+        node_name: String,
+    },
+    /// Assign protected identifiers to a range of configurable frames of the node (LIN 2.1)
+    AssignFrameIdRange {
+        /// Node name
+        node_name: String,
+        /// Index of the first configurable frame
+        frame_index: u8,
+        /// Protected identifiers, either none (taken from the LDF) or exactly four
+        frame_pids: Vec<u8>,
+    },
+    /// Free format master request (LIN 2.1)
+    FreeFormat {
+        /// Data bytes D1 to D8
+        data: [u8; 8],
+    },
+    /// Assign the frame identifier of a frame to the node (LIN 2.0)
+    AssignFrameId {
+        /// Node name
+        node_name: String,
+        /// Frame name
+        frame_name: String,
+    },
+    /// Unassign the frame identifier of a frame from the node (LIN 2.0)
+    UnassignFrameId {
+        /// Node name
+        node_name: String,
+        /// Frame name
+        frame_name: String,
+    },
+}
+
+impl ResolveSpans for LdfScheduleTable {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+        self.frame_delays.resolve_spans(index);
+    }
+}
+
+impl ResolveSpans for LdfFrameDelay {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+        self.references.resolve_spans(index);
+    }
 }
 
 /*
@@ -46,6 +179,10 @@ Schedule_tables {
      Frame1 delay 10 ms ;
      Frame2   delay 10.0 ms ;
   }
+  Configuration {
+     AssignNAD { Slave1 } delay 20 ms ;
+     AssignFrameIdRange { Slave1, 0 } delay 20 ms ;
+  }
 }
 */
 
@@ -66,6 +203,7 @@ pub fn parse_ldf_schedule_tables(s: &str) -> IResult<&str, Vec<LdfScheduleTable>
         // - May be any number of spaces before and after the "AllFrames" tag
         // - May be any number of spaces before and after the opening curly brace
         let (s, _) = skip_whitespace(remaining)?;
+        let entry = s;
         let (s, schedule_table_name) = take_while(|c: char| c.is_alphanumeric() || c == '_')(s)?;
         let (s, _) = skip_whitespace(s)?;
         let (s, _) = tag("{")(s)?;
@@ -74,38 +212,43 @@ pub fn parse_ldf_schedule_tables(s: &str) -> IResult<&str, Vec<LdfScheduleTable>
         remaining = s;
 
         while !remaining.starts_with('}') {
-            // `Frame1 delay 10 ms ;` or `Frame1 delay 10 ms;` or ...
-            // - May be any number of spaces before and after the frame name
+            // `Frame1 delay 10 ms ;` or `AssignNAD { Slave1 } delay 20 ms;` or ...
+            // - May be any number of spaces before and after the command
             // - May be any number of spaces before and after the "delay" tag
             // - May be any number of spaces before and after the frame delay
             // - May be any number of spaces before and after the "ms" tag
             // - May be any number of spaces before and after the semicolon
             let (s, _) = skip_whitespace(remaining)?;
-            let (s, frame_name) = take_while(|c: char| c.is_alphanumeric() || c == '_')(s)?;
+            let delay_entry = s;
+            let (s, command) = parse_ldf_schedule_command(s)?;
+            let references = command_references(delay_entry, &delay_entry[..delay_entry.len() - s.len()], &command);
             let (s, _) = skip_whitespace(s)?;
             let (s, _) = tag("delay")(s)?;
             let (s, _) = skip_whitespace(s)?;
-            let (s, frame_time) = take_while(|c: char| c.is_numeric() || c == '.')(s)?;
-            let (s, _) = skip_whitespace(s)?;
-            let (s, _) = tag("ms")(s)?;
+            let (s, frame_time) = parse_milliseconds_value(s)?;
             let (s, _) = skip_whitespace(s)?;
             let (s, _) = tag(";")(s)?;
+            let span = LdfSpan::new(delay_entry, s);
             let (s, _) = skip_whitespace(s)?;
 
             frame_delays.push(LdfFrameDelay {
-                frame_name: frame_name.to_string(),
-                frame_time: frame_time.parse().unwrap(),
+                command,
+                frame_time: frame_time as f32,
+                span,
+                references,
             });
 
             remaining = s;
         }
 
         let (s, _) = tag("}")(remaining)?;
+        let span = LdfSpan::new(entry, s);
         let (s, _) = skip_whitespace(s)?;
 
         schedule_tables.push(LdfScheduleTable {
             schedule_table_name: schedule_table_name.to_string(),
             frame_delays,
+            span,
         });
 
         remaining = s;
@@ -116,9 +259,116 @@ pub fn parse_ldf_schedule_tables(s: &str) -> IResult<&str, Vec<LdfScheduleTable>
     Ok((remaining, schedule_tables))
 }
 
+fn parse_ldf_schedule_command(s: &str) -> IResult<&str, LdfScheduleCommand> {
+    // `Frame1` or `MasterReq` or `AssignNAD { Slave1 }` or `FreeFormat{0x3C,0xB2,0,0,0,0,0,0}` or ...
+    // - May be any number of spaces before and after the curly braces
+    // - May be any number of spaces before and after each comma separated argument
+    let (s, name) = take_while(|c: char| c.is_alphanumeric() || c == '_')(s)?;
+    let (rest, _) = skip_whitespace(s)?;
+
+    // Commands without arguments are either frames or the diagnostic frames
+    if !rest.starts_with('{') {
+        let command = match name {
+            "MasterReq" => LdfScheduleCommand::MasterReq,
+            "SlaveResp" => LdfScheduleCommand::SlaveResp,
+            _ => LdfScheduleCommand::Frame {
+                frame_name: name.to_string(),
+            },
+        };
+        return Ok((s, command));
+    }
+
+    let (s, _) = tag("{")(rest)?;
+    let (s, arguments) = take_until("}")(s)?;
+    let (s, _) = tag("}")(s)?;
+    let arguments: Vec<&str> = arguments.split(',').map(|argument| argument.trim()).collect();
+
+    let invalid = || nom::Err::Error(LdfParseError::new(rest, ErrorKind::Verify));
+    let command = match (name, arguments.as_slice()) {
+        ("AssignNAD", [node_name]) => LdfScheduleCommand::AssignNad {
+            node_name: node_name.to_string(),
+        },
+        ("ConditionalChangeNAD", [nad, id, byte, mask, inv, new_nad]) => LdfScheduleCommand::ConditionalChangeNad {
+            nad: parse_integer_token(nad)?.1,
+            id: parse_integer_token(id)?.1,
+            byte: parse_integer_token(byte)?.1,
+            mask: parse_integer_token(mask)?.1,
+            inv: parse_integer_token(inv)?.1,
+            new_nad: parse_integer_token(new_nad)?.1,
+        },
+        ("DataDump", [node_name, data @ ..]) if data.len() == 5 => LdfScheduleCommand::DataDump {
+            node_name: node_name.to_string(),
+            data: parse_data_bytes(data).ok_or_else(invalid)?,
+        },
+        ("SaveConfiguration", [node_name]) => LdfScheduleCommand::SaveConfiguration {
+            node_name: node_name.to_string(),
+        },
+        ("AssignFrameIdRange", [node_name, frame_index, frame_pids @ ..])
+            if frame_pids.is_empty() || frame_pids.len() == 4 =>
+        {
+            LdfScheduleCommand::AssignFrameIdRange {
+                node_name: node_name.to_string(),
+                frame_index: parse_integer_token(frame_index)?.1,
+                frame_pids: match frame_pids.is_empty() {
+                    true => Vec::new(),
+                    false => parse_data_bytes::<4>(frame_pids).ok_or_else(invalid)?.to_vec(),
+                },
+            }
+        }
+        ("FreeFormat", data) if data.len() == 8 => LdfScheduleCommand::FreeFormat {
+            data: parse_data_bytes(data).ok_or_else(invalid)?,
+        },
+        ("AssignFrameId", [node_name, frame_name]) => LdfScheduleCommand::AssignFrameId {
+            node_name: node_name.to_string(),
+            frame_name: frame_name.to_string(),
+        },
+        ("UnassignFrameId", [node_name, frame_name]) => LdfScheduleCommand::UnassignFrameId {
+            node_name: node_name.to_string(),
+            frame_name: frame_name.to_string(),
+        },
+        // Unknown command or wrong number of arguments
+        _ => return Err(invalid()),
+    };
+
+    Ok((s, command))
+}
+
+/// Frames and nodes referenced by a command, e.g. `Slave1` and `Frame1` of `AssignFrameId { Slave1, Frame1 }`.
+/// `text` is the command at the start of the remaining input `s`.
+fn command_references(s: &str, text: &str, command: &LdfScheduleCommand) -> Vec<LdfNamedSpan> {
+    // The node name and the frame name are always the first arguments
+    let count = match command {
+        LdfScheduleCommand::Frame { .. } => return list_spans(s, text),
+        LdfScheduleCommand::MasterReq
+        | LdfScheduleCommand::SlaveResp
+        | LdfScheduleCommand::ConditionalChangeNad { .. }
+        | LdfScheduleCommand::FreeFormat { .. } => 0,
+        LdfScheduleCommand::AssignNad { .. }
+        | LdfScheduleCommand::DataDump { .. }
+        | LdfScheduleCommand::SaveConfiguration { .. }
+        | LdfScheduleCommand::AssignFrameIdRange { .. } => 1,
+        LdfScheduleCommand::AssignFrameId { .. } | LdfScheduleCommand::UnassignFrameId { .. } => 2,
+    };
+    let arguments = text.find('{').map_or("", |i| text[i + 1..].trim_end_matches('}'));
+    list_spans(s, arguments).into_iter().take(count).collect()
+}
+
+/// Data bytes of a command, e.g. `0x3C, 0xB2, 0, 0, 0` of a `DataDump`
+fn parse_data_bytes<const N: usize>(data: &[&str]) -> Option<[u8; N]> {
+    let mut bytes = [0; N];
+    if data.len() != N {
+        return None;
+    }
+    for (byte, token) in bytes.iter_mut().zip(data) {
+        *byte = parse_integer_token::<u8>(token).ok()?.1;
+    }
+    Some(bytes)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ldf::ldf_error::LdfError;
 
     #[test]
     fn test_parse_ldf_schedule_tables() {
@@ -140,11 +390,159 @@ mod tests {
         assert_eq!(schedule_table.frame_delays.len(), 2);
 
         let frame_delay = &schedule_table.frame_delays[0];
-        assert_eq!(frame_delay.frame_name, "Frame1");
+        assert_eq!(
+            frame_delay.command,
+            LdfScheduleCommand::Frame {
+                frame_name: "Frame1".to_string()
+            }
+        );
         assert_eq!(frame_delay.frame_time, 10.0);
 
         let frame_delay = &schedule_table.frame_delays[1];
-        assert_eq!(frame_delay.frame_name, "Frame2");
+        assert_eq!(
+            frame_delay.command,
+            LdfScheduleCommand::Frame {
+                frame_name: "Frame2".to_string()
+            }
+        );
         assert_eq!(frame_delay.frame_time, 10.0);
     }
+
+    #[test]
+    fn test_parse_ldf_schedule_tables_commands() {
+        let input = r#"
+            Schedule_tables {
+                Configuration {
+                    MasterReq delay 10 ms ;
+                    SlaveResp delay 10 ms ;
+                    AssignNAD { Slave1 } delay 20 ms ;
+                    ConditionalChangeNAD { 0x7F, 0x01, 0x03, 0x01, 0xFF, 0x01 } delay 20 ms ;
+                    DataDump{Slave1,0x10,0x80,0x00,0xFF,0} delay 20 ms ;
+                    SaveConfiguration { Slave1 } delay 20 ms ;
+                    AssignFrameIdRange { Slave1, 0 } delay 20 ms ;
+                    AssignFrameIdRange { Slave1, 2, 0x80, 0xC1, 0x42, 0xFF } delay 20 ms ;
+                    FreeFormat { 0x3C, 0xB2, 0x00, 0x00, 0xFF, 0x7F, 0xFF, 0xFF } delay 20 ms ;
+                    AssignFrameId { Slave1, Frame1 } delay 20 ms ;
+                    UnassignFrameId { Slave1, Frame1 } delay 20 ms ;
+                }
+            }
+        "#;
+
+        let (_, schedule_tables) = parse_ldf_schedule_tables(input).unwrap();
+        assert_eq!(schedule_tables.len(), 1);
+
+        let commands: Vec<&LdfScheduleCommand> = schedule_tables[0]
+            .frame_delays
+            .iter()
+            .map(|frame_delay| &frame_delay.command)
+            .collect();
+        assert_eq!(commands.len(), 11);
+        assert_eq!(*commands[0], LdfScheduleCommand::MasterReq);
+        assert_eq!(*commands[1], LdfScheduleCommand::SlaveResp);
+        assert_eq!(
+            *commands[2],
+            LdfScheduleCommand::AssignNad {
+                node_name: "Slave1".to_string()
+            }
+        );
+        assert_eq!(
+            *commands[3],
+            LdfScheduleCommand::ConditionalChangeNad {
+                nad: 0x7F,
+                id: 0x01,
+                byte: 0x03,
+                mask: 0x01,
+                inv: 0xFF,
+                new_nad: 0x01,
+            }
+        );
+        assert_eq!(
+            *commands[4],
+            LdfScheduleCommand::DataDump {
+                node_name: "Slave1".to_string(),
+                data: [0x10, 0x80, 0x00, 0xFF, 0x00],
+            }
+        );
+        assert_eq!(
+            *commands[5],
+            LdfScheduleCommand::SaveConfiguration {
+                node_name: "Slave1".to_string()
+            }
+        );
+        assert_eq!(
+            *commands[6],
+            LdfScheduleCommand::AssignFrameIdRange {
+                node_name: "Slave1".to_string(),
+                frame_index: 0,
+                frame_pids: vec![],
+            }
+        );
+        assert_eq!(
+            *commands[7],
+            LdfScheduleCommand::AssignFrameIdRange {
+                node_name: "Slave1".to_string(),
+                frame_index: 2,
+                frame_pids: vec![0x80, 0xC1, 0x42, 0xFF],
+            }
+        );
+        assert_eq!(
+            *commands[8],
+            LdfScheduleCommand::FreeFormat {
+                data: [0x3C, 0xB2, 0x00, 0x00, 0xFF, 0x7F, 0xFF, 0xFF],
+            }
+        );
+        assert_eq!(
+            *commands[9],
+            LdfScheduleCommand::AssignFrameId {
+                node_name: "Slave1".to_string(),
+                frame_name: "Frame1".to_string(),
+            }
+        );
+        assert_eq!(
+            *commands[10],
+            LdfScheduleCommand::UnassignFrameId {
+                node_name: "Slave1".to_string(),
+                frame_name: "Frame1".to_string(),
+            }
+        );
+        assert!(schedule_tables[0]
+            .frame_delays
+            .iter()
+            .all(|frame_delay| frame_delay.frame_time >= 10.0));
+    }
+
+    #[test]
+    fn test_parse_ldf_schedule_tables_invalid_command() {
+        let input = r#"
+            Schedule_tables {
+                Configuration {
+                    FreeFormat { 0x3C, 0xB2 } delay 20 ms ;
+                }
+            }
+        "#;
+
+        assert!(parse_ldf_schedule_tables(input).is_err());
+    }
+
+    #[test]
+    fn test_parse_ldf_schedule_tables_negative_delay() {
+        let input = r#"
+            Schedule_tables {
+                Table1 {
+                    Frame1 delay -10 ms ;
+                }
+            }
+        "#;
+
+        let error = parse_ldf_schedule_tables(input).err().unwrap();
+        let error = LdfError::from_parse_error(input, "Schedule_tables", error);
+        assert_eq!(error.message, "value out of range");
+        assert_eq!(&input[error.offset..error.offset + 3], "-10");
+        assert_eq!(error.hint().as_deref(), Some("times can't be negative, e.g. `10 ms`"));
+
+        for delay in ["1E999 ms", "10"] {
+            let input = input.replace("-10 ms", delay);
+            assert!(parse_ldf_schedule_tables(&input).is_err(), "Frame1 delay {} ;", delay);
+        }
+    }
 }
@@ -1,8 +1,8 @@
 use crate::ldf::ldf_comment::skip_whitespace;
-use nom::{
-    bytes::complete::{tag, take_until, take_while},
-    IResult,
-};
+use crate::ldf::ldf_error::{tag, take_until, IResult, LdfParseError};
+use crate::ldf::ldf_number::{parse_integer, parse_real, parse_signed_integer};
+use crate::ldf::ldf_span::{LdfSpan, LineIndex, ResolveSpans};
+use nom::{bytes::complete::take_while, error::ErrorKind};
 
 /// `Signal_encoding_types` section of a LIN Description File (LDF)
 /// ```text
@@ -17,6 +17,9 @@ use nom::{
 ///   ENC_ENGINE_RPM {
 ///     physical_value, 0, 1023, 10, 0, "RPM" ;
 ///   }
+///   ENC_SERIAL_NUMBER {
+///     ascii_value ;
+///   }
 /// }
 /// ```
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -28,6 +31,16 @@ pub struct LdfSignalEncodingType {
 
     /// Signal encoding type values
     pub encoding_type_values: Vec<LdfSignalEncodingTypeValue>,
+
+    /// Location of the encoding type in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
+
+    /// Location of each of the `encoding_type_values` in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub value_spans: Vec<LdfSpan>,
 }
 
 /// Signal encoding type value in the `Signal_encoding_types` section of a LIN Description File (LDF)
@@ -36,23 +49,34 @@ pub struct LdfSignalEncodingType {
 #[cfg_attr(feature = "ts-rs", ts(export))]
 pub enum LdfSignalEncodingTypeValue {
     LogicalValue {
-        /// Value
+        /// Raw signal value
         value: u32,
         /// Value description
         value_description: String,
     },
     PhysicalValue {
-        /// Minimum value
+        /// Minimum raw signal value
         min_value: i32,
-        /// Maximum value
+        /// Maximum raw signal value
         max_value: i32,
-        /// Scaling factor
-        scaling_factor: f32,
+        /// Scaling factor (physical value = scaling factor * raw value + offset)
+        scaling_factor: f64,
         /// Offset
-        offset: f32,
+        offset: f64,
         /// Unit
         unit: String,
     },
+    /// The signal is encoded as binary coded decimal (`bcd_value ;`)
+    BcdValue,
+    /// The signal is encoded as ASCII characters (`ascii_value ;`)
+    AsciiValue,
+}
+
+impl ResolveSpans for LdfSignalEncodingType {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+        self.value_spans.resolve_spans(index);
+    }
 }
 
 /*
@@ -94,15 +118,20 @@ pub fn parse_ldf_signal_encoding_types(s: &str) -> IResult<&str, Vec<LdfSignalEn
         // - May be any number of spaces before and after the signal encoding type name
         // - May be any number of spaces before and after the opening curly brace
         let (s, _) = skip_whitespace(remaining)?;
+        let entry = s;
         let (s, encoding_type_name) = take_while(|c: char| c.is_alphanumeric() || c == '_')(s)?;
         let (s, _) = skip_whitespace(s)?;
         let (s, _) = tag("{")(s)?;
 
         let mut encoding_type_values = Vec::new();
+        let mut value_spans = Vec::new();
         remaining = s;
 
         while !remaining.starts_with('}') {
-            // `logical_value, 0, "FALSE" ;` or `logical_value, 0, "FALSE";` or ...
+            // `logical_value, 0, "FALSE" ;` or `logical_value, 0x0F, "Error";` or ...
+            // `physical_value, 0, 0xFFFE, 0.5, -40, "degC" ;` or `physical_value, 0, 5, 1E-05, .5 ;` or ...
+            // - Raw values may be decimal or hexadecimal integers
+            // - Scaling factors and offsets may have a sign, a fraction and an exponent
             // - May be any number of spaces before and after the value type
             // - May be any number of spaces before and after the comma
             // - May be any number of spaces before and after the value
@@ -110,13 +139,16 @@ pub fn parse_ldf_signal_encoding_types(s: &str) -> IResult<&str, Vec<LdfSignalEn
             // - May be any number of spaces before and after the value description
             // - May be any number of spaces before and after the semicolon
             let (s, _) = skip_whitespace(remaining)?;
-            let (s, value_type) = take_until(",")(s)?;
-            let (s, _) = tag(",")(s)?;
+            let value_entry = s;
+            let (s, value_type) = take_while(|c: char| c.is_alphanumeric() || c == '_')(s)?;
             let (s, _) = skip_whitespace(s)?;
 
             let (s, encoding_type_value) = match value_type {
                 "logical_value" => {
-                    let (s, value) = take_while(|c: char| c.is_numeric())(s)?;
+                    let (s, _) = tag(",")(s)?;
+                    let (s, _) = skip_whitespace(s)?;
+                    let (s, value) = parse_integer(s)?;
+                    let (s, _) = skip_whitespace(s)?;
                     let (s, _) = tag(",")(s)?;
                     let (s, _) = skip_whitespace(s)?;
                     let (s, _) = tag("\"")(s)?;
@@ -124,36 +156,35 @@ pub fn parse_ldf_signal_encoding_types(s: &str) -> IResult<&str, Vec<LdfSignalEn
                     let (s, _) = tag("\"")(s)?;
                     let (s, _) = skip_whitespace(s)?;
                     let (s, _) = tag(";")(s)?;
-                    let (s, _) = skip_whitespace(s)?;
                     (
                         s,
                         LdfSignalEncodingTypeValue::LogicalValue {
-                            value: value.parse().unwrap(),
+                            value,
                             value_description: value_description.to_string(),
                         },
                     )
                 }
                 "physical_value" => {
-                    let (s, min_value) = take_while(|c: char| c.is_numeric() || c == '-')(s)?;
+                    let (s, _) = tag(",")(s)?;
+                    let (s, _) = skip_whitespace(s)?;
+                    let (s, min_value) = parse_signed_integer(s)?;
                     let (s, _) = skip_whitespace(s)?;
                     let (s, _) = tag(",")(s)?;
                     let (s, _) = skip_whitespace(s)?;
-                    let (s, max_value) = take_while(|c: char| c.is_numeric() || c == '-')(s)?;
+                    let (s, max_value) = parse_signed_integer(s)?;
                     let (s, _) = skip_whitespace(s)?;
                     let (s, _) = tag(",")(s)?;
                     let (s, _) = skip_whitespace(s)?;
-                    let (s, scaling_factor) =
-                        take_while(|c: char| c.is_numeric() || c == 'e' || c == 'E' || c == '.' || c == '-')(s)?;
+                    let (s, scaling_factor) = parse_real(s)?;
                     let (s, _) = skip_whitespace(s)?;
                     let (s, _) = tag(",")(s)?;
                     let (s, _) = skip_whitespace(s)?;
-                    let (s, offset) = take_while(|c: char| c.is_numeric() || c == '.' || c == '-')(s)?;
+                    let (s, offset) = parse_real(s)?;
                     let (s, _) = skip_whitespace(s)?;
 
                     // Allow the unit to be left out (implied as an empty string)
                     let (s, unit) = if s.starts_with(';') {
                         let (s, _) = tag(";")(s)?;
-                        let (s, _) = skip_whitespace(s)?;
                         (s, "")
                     } else {
                         let (s, _) = tag(",")(s)?;
@@ -163,48 +194,49 @@ pub fn parse_ldf_signal_encoding_types(s: &str) -> IResult<&str, Vec<LdfSignalEn
                         let (s, _) = tag("\"")(s)?;
                         let (s, _) = skip_whitespace(s)?;
                         let (s, _) = tag(";")(s)?;
-                        let (s, _) = skip_whitespace(s)?;
                         (s, unit)
                     };
 
-                    // Scaling factor may be in scientific notation (ex: 1E-05)
-                    let scaling_factor = {
-                        if scaling_factor.contains('e') || scaling_factor.contains('E') {
-                            let scaling_factor = scaling_factor.replace("e", "E").replace(" ", "");
-                            let base = scaling_factor.split("E").collect::<Vec<&str>>()[0];
-                            let exponent = scaling_factor.split("E").collect::<Vec<&str>>()[1];
-                            let base_f32 = base.parse::<f32>().unwrap();
-                            let exp_f32 = exponent.parse::<f32>().unwrap();
-                            base_f32 * 10_f32.powf(exp_f32)
-                        } else {
-                            scaling_factor.parse::<f32>().unwrap()
-                        }
-                    };
-
                     (
                         s,
                         LdfSignalEncodingTypeValue::PhysicalValue {
-                            min_value: min_value.parse().unwrap(),
-                            max_value: max_value.parse().unwrap(),
+                            min_value,
+                            max_value,
                             scaling_factor,
-                            offset: offset.parse().unwrap(),
+                            offset,
                             unit: unit.to_string(),
                         },
                     )
                 }
-                _ => panic!("Unknown value type: {}", value_type),
+                // `bcd_value ;` or `ascii_value ;`
+                // - May be any number of spaces before and after the semicolon
+                "bcd_value" | "ascii_value" => {
+                    let (s, _) = tag(";")(s)?;
+                    let encoding_type_value = match value_type {
+                        "bcd_value" => LdfSignalEncodingTypeValue::BcdValue,
+                        _ => LdfSignalEncodingTypeValue::AsciiValue,
+                    };
+                    (s, encoding_type_value)
+                }
+                // Unknown value type
+                _ => return Err(nom::Err::Error(LdfParseError::new(remaining, ErrorKind::Tag))),
             };
 
+            value_spans.push(LdfSpan::new(value_entry, s));
             encoding_type_values.push(encoding_type_value);
+            let (s, _) = skip_whitespace(s)?;
             remaining = s;
         }
 
         let (s, _) = tag("}")(remaining)?;
+        let span = LdfSpan::new(entry, s);
         let (s, _) = skip_whitespace(s)?;
 
         signal_encoding_types.push(LdfSignalEncodingType {
             encoding_type_name: encoding_type_name.to_string(),
             encoding_type_values,
+            span,
+            value_spans,
         });
 
         remaining = s;
@@ -409,4 +441,106 @@ mod tests {
             _ => panic!("Expected PhysicalValue"),
         }
     }
+
+    #[test]
+    fn test_parse_ldf_signal_encoding_types_bcd_and_ascii() {
+        let input = r#"
+            Signal_encoding_types {
+                ENC_BCD {
+                    bcd_value ;
+                }
+                ENC_ASCII {
+                    ascii_value;
+                }
+                ENC_MIXED {
+                    logical_value, 0, "OFF" ;
+                    bcd_value ;
+                }
+            }
+        "#;
+        let (_, signal_encoding_types) = parse_ldf_signal_encoding_types(input).unwrap();
+        assert_eq!(signal_encoding_types.len(), 3);
+        assert!(matches!(
+            signal_encoding_types[0].encoding_type_values[..],
+            [LdfSignalEncodingTypeValue::BcdValue]
+        ));
+        assert!(matches!(
+            signal_encoding_types[1].encoding_type_values[..],
+            [LdfSignalEncodingTypeValue::AsciiValue]
+        ));
+        assert!(matches!(
+            signal_encoding_types[2].encoding_type_values[..],
+            [
+                LdfSignalEncodingTypeValue::LogicalValue { .. },
+                LdfSignalEncodingTypeValue::BcdValue
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_parse_ldf_signal_encoding_types_unknown_value_type() {
+        let input = r#"
+            Signal_encoding_types {
+                ENC_UNKNOWN {
+                    unknown_value, 0, "OFF" ;
+                }
+            }
+        "#;
+        assert!(parse_ldf_signal_encoding_types(input).is_err());
+    }
+
+    #[test]
+    fn test_parse_ldf_signal_encoding_types_hex_and_real_numbers() {
+        let input = r#"
+            Signal_encoding_types {
+                ENC_STATUS {
+                    logical_value, 0x0F, "Error" ;
+                    logical_value , 0x0E , "Not available" ;
+                }
+                ENC_TEMP {
+                    physical_value, 0, 0xFFFE, 0.5, -40, "degC" ;
+                    physical_value, 0xFFFF, 0xFFFF, .5, +1.5e+2, "invalid" ;
+                }
+            }
+        "#;
+        let (_, signal_encoding_types) = parse_ldf_signal_encoding_types(input).unwrap();
+        assert_eq!(signal_encoding_types.len(), 2);
+
+        match &signal_encoding_types[0].encoding_type_values[..] {
+            [LdfSignalEncodingTypeValue::LogicalValue { value: first, .. }, LdfSignalEncodingTypeValue::LogicalValue {
+                value: second,
+                value_description,
+            }] => {
+                assert_eq!(*first, 0x0F);
+                assert_eq!(*second, 0x0E);
+                assert_eq!(value_description, "Not available");
+            }
+            _ => panic!("Expected two LogicalValues"),
+        }
+
+        match &signal_encoding_types[1].encoding_type_values[..] {
+            [LdfSignalEncodingTypeValue::PhysicalValue {
+                min_value,
+                max_value,
+                scaling_factor,
+                offset,
+                unit,
+            }, LdfSignalEncodingTypeValue::PhysicalValue {
+                min_value: invalid_min_value,
+                scaling_factor: invalid_scaling_factor,
+                offset: invalid_offset,
+                ..
+            }] => {
+                assert_eq!(*min_value, 0);
+                assert_eq!(*max_value, 0xFFFE);
+                assert_eq!(*scaling_factor, 0.5);
+                assert_eq!(*offset, -40.0);
+                assert_eq!(unit, "degC");
+                assert_eq!(*invalid_min_value, 0xFFFF);
+                assert_eq!(*invalid_scaling_factor, 0.5);
+                assert_eq!(*invalid_offset, 150.0);
+            }
+            _ => panic!("Expected two PhysicalValues"),
+        }
+    }
 }
@@ -0,0 +1,211 @@
+use crate::ldf::ldf_comment::skip_whitespace;
+use crate::ldf::ldf_error::{tag, IResult};
+use crate::ldf::ldf_identifier::parse_identifier;
+use crate::ldf::ldf_number::parse_integer;
+use crate::ldf::ldf_span::{LdfNamedSpan, LdfSpan, LineIndex, ResolveSpans};
+
+/// `Signal_groups` section of a LIN Description File (LDF)
+///
+/// Signal groups are deprecated since LIN 2.1 but still found in older files.
+/// ```text
+/// Signal_groups {
+///   Group1: 16 {
+///     Signal1, 0 ;
+///     Signal2, 8 ;
+///   }
+/// }
+/// ```
+/// ---
+/// Reads as:
+/// ```text
+/// <signal_group_name>: <group_size> {
+///   <signal_name>, <group_offset> ;
+/// }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub struct LdfSignalGroup {
+    /// Signal group name
+    pub name: String,
+
+    /// Size of the signal group in bits
+    pub group_size: u8,
+
+    /// Signals in the group with their bit offset within the group
+    pub signals: Vec<(String, u8)>,
+
+    /// Location of the signal group in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
+
+    /// Identifiers referenced by the signal group (signals), with their location in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub references: Vec<LdfNamedSpan>,
+}
+
+impl ResolveSpans for LdfSignalGroup {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+        self.references.resolve_spans(index);
+    }
+}
+
+/*
+Signal_groups {
+  Group1: 16 {
+    Signal1, 0 ;
+    Signal2, 8 ;
+  }
+}
+*/
+
+pub fn parse_ldf_signal_groups(s: &str) -> IResult<&str, Vec<LdfSignalGroup>> {
+    // `Signal_groups {` or `Signal_groups{` or ...
+    // - May be any number of spaces before and after the "Signal_groups" tag
+    // - May be any number of spaces before and after the opening curly brace
+    let (s, _) = skip_whitespace(s)?;
+
+    // Assume that if the section label is not present, then the section is not present.
+    if !s.starts_with("Signal_groups") {
+        return Ok((s, Vec::new()));
+    }
+
+    let (s, _) = tag("Signal_groups")(s)?;
+    let (s, _) = skip_whitespace(s)?;
+    let (s, _) = tag("{")(s)?;
+    let (s, _) = skip_whitespace(s)?;
+
+    let mut signal_groups = Vec::new();
+    let mut remaining = s;
+
+    while !remaining.starts_with('}') {
+        // `Group1: 16 {` or `Group1:16{` or ...
+        // - May be any number of spaces before and after the signal group name
+        // - May be any number of spaces before and after the colon
+        // - May be any number of spaces before and after the group size
+        // - May be any number of spaces before and after the opening curly brace
+        let (s, _) = skip_whitespace(remaining)?;
+        let entry = s;
+        let (s, name) = parse_identifier(s)?;
+        let (s, _) = skip_whitespace(s)?;
+        let (s, _) = tag(":")(s)?;
+        let (s, _) = skip_whitespace(s)?;
+        let (s, group_size) = parse_integer(s)?;
+        let (s, _) = skip_whitespace(s)?;
+        let (s, _) = tag("{")(s)?;
+        let (s, _) = skip_whitespace(s)?;
+
+        let mut signals = Vec::new();
+        let mut references = Vec::new();
+        remaining = s;
+
+        while !remaining.starts_with('}') {
+            // `Signal1, 0 ;` or `Signal1, 0;` or ...
+            // - May be any number of spaces before and after the signal name
+            // - May be any number of spaces before and after the comma
+            // - May be any number of spaces before and after the group offset
+            // - May be any number of spaces before and after the semicolon
+            let (s, signal_name) = parse_identifier(remaining)?;
+            let (s, _) = skip_whitespace(s)?;
+            let (s, _) = tag(",")(s)?;
+            let (s, _) = skip_whitespace(s)?;
+            let (s, group_offset) = parse_integer(s)?;
+            let (s, _) = skip_whitespace(s)?;
+            let (s, _) = tag(";")(s)?;
+            let (s, _) = skip_whitespace(s)?;
+
+            signals.push((signal_name.to_string(), group_offset));
+            references.push(LdfNamedSpan::of(remaining, signal_name));
+
+            remaining = s;
+        }
+
+        let (s, _) = tag("}")(remaining)?;
+        let span = LdfSpan::new(entry, s);
+        let (s, _) = skip_whitespace(s)?;
+
+        signal_groups.push(LdfSignalGroup {
+            name: name.to_string(),
+            group_size,
+            signals,
+            span,
+            references,
+        });
+
+        remaining = s;
+    }
+
+    let (remaining, _) = tag("}")(remaining)?;
+
+    Ok((remaining, signal_groups))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ldf_signal_groups() {
+        let input = r#"
+            Signal_groups {
+                Group1: 16 {
+                    Signal1, 0 ;
+                    Signal2, 8 ;
+                }
+                Group2 : 8 {
+                    Signal3,0;
+                }
+            }
+        "#;
+
+        let (_, signal_groups) = parse_ldf_signal_groups(input).unwrap();
+        assert_eq!(signal_groups.len(), 2);
+
+        assert_eq!(signal_groups[0].name, "Group1");
+        assert_eq!(signal_groups[0].group_size, 16);
+        assert_eq!(signal_groups[0].signals.len(), 2);
+        assert_eq!(signal_groups[0].signals[0], ("Signal1".to_string(), 0));
+        assert_eq!(signal_groups[0].signals[1], ("Signal2".to_string(), 8));
+
+        assert_eq!(signal_groups[1].name, "Group2");
+        assert_eq!(signal_groups[1].group_size, 8);
+        assert_eq!(signal_groups[1].signals, vec![("Signal3".to_string(), 0)]);
+    }
+
+    #[test]
+    fn test_parse_ldf_signal_groups_comments() {
+        let input = r#"
+            Signal_groups {
+                Group1: 16 { // lights
+                    Signal1 /* low byte */ , 0 ;
+                    // Signal2, 8 ;
+                    Signal3, /* high byte */ 8 ;
+                }
+            }
+        "#;
+
+        let (_, signal_groups) = parse_ldf_signal_groups(input).unwrap();
+        assert_eq!(
+            signal_groups[0].signals,
+            vec![("Signal1".to_string(), 0), ("Signal3".to_string(), 8)]
+        );
+        let references: Vec<&str> = signal_groups[0]
+            .references
+            .iter()
+            .map(|reference| reference.name.as_str())
+            .collect();
+        assert_eq!(references, vec!["Signal1", "Signal3"]);
+
+        let input = r#"
+            Signal_groups {
+                Group1: 16 {
+                    , 0 ;
+                }
+            }
+        "#;
+        assert!(parse_ldf_signal_groups(input).is_err());
+    }
+}
@@ -1,8 +1,7 @@
 use crate::ldf::ldf_comment::skip_whitespace;
-use nom::{
-    bytes::complete::{tag, take_until, take_while},
-    IResult,
-};
+use crate::ldf::ldf_error::{tag, take_until, IResult};
+use crate::ldf::ldf_span::{list_spans, LdfNamedSpan, LdfSpan, LineIndex, ResolveSpans};
+use nom::bytes::complete::take_while;
 
 /// `Signal_representation` section of a LIN Description File (LDF)
 /// ```text
@@ -20,6 +19,23 @@ pub struct LdfSignalRepresentation {
 
     /// Signal names
     pub signal_names: Vec<String>,
+
+    /// Location of the representation in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
+
+    /// Identifiers referenced by the representation (encoding type, signals), with their location in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub references: Vec<LdfNamedSpan>,
+}
+
+impl ResolveSpans for LdfSignalRepresentation {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+        self.references.resolve_spans(index);
+    }
 }
 
 /*
@@ -54,19 +70,25 @@ pub fn parse_ldf_signal_representation(s: &str) -> IResult<&str, Vec<LdfSignalRe
         // - May be any number of spaces before and after the colon
         // - May be any number of spaces before and after the semicolon
         let (s, _) = skip_whitespace(remaining)?;
+        let entry = s;
         let (s, encoding_type_name) = take_while(|c: char| c.is_alphanumeric() || c == '_')(s)?;
         let (s, _) = skip_whitespace(s)?;
         let (s, _) = tag(":")(s)?;
         let (s, _) = skip_whitespace(s)?;
         let (s, signal_names) = take_until(";")(s)?;
         let (s, _) = tag(";")(s)?;
+        let span = LdfSpan::new(entry, s);
         let (s, _) = skip_whitespace(s)?;
 
+        let mut references = vec![LdfNamedSpan::of(entry, encoding_type_name)];
+        references.extend(list_spans(entry, signal_names));
         let signal_names = signal_names.split(',').map(|s| s.trim().to_string()).collect();
 
         signal_representations.push(LdfSignalRepresentation {
             encoding_type_name: encoding_type_name.trim().to_string(),
             signal_names,
+            span,
+            references,
         });
 
         remaining = s;
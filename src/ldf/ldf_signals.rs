@@ -1,8 +1,8 @@
 use crate::ldf::ldf_comment::skip_whitespace;
-use nom::{
-    bytes::complete::{tag, take_until, take_while},
-    IResult,
-};
+use crate::ldf::ldf_error::{tag, take_until, IResult, LdfParseError};
+use crate::ldf::ldf_number::{parse_integer, parse_integer_token};
+use crate::ldf::ldf_span::{list_spans, LdfNamedSpan, LdfSpan, LineIndex, ResolveSpans};
+use nom::{bytes::complete::take_while, error::ErrorKind};
 
 /// The init_value specifies the signal value that shall be used by all subscriber nodes.
 /// The init_value_scalar is used for scalar signals and the init_value_array is used for byte array signals.
@@ -11,7 +11,9 @@ use nom::{
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
 pub enum LdfSignalInitValue {
-    Scalar(u8),
+    /// Scalar signals are 1 to 16 bits long
+    Scalar(u16),
+    /// Byte arrays are 1 to 8 bytes long, most significant byte first
     Array(Vec<u8>),
 }
 
@@ -52,6 +54,12 @@ pub struct LdfSignal {
     /// <init_value_array> ::= {integer ([, integer])}
     /// ```
     ///
+    /// Integers may be written in decimal (`255`) or hexadecimal (`0xFF`) notation:
+    /// ```text
+    /// Signal1: 16, 0x1FF, Master, Slave1 ;
+    /// Signal2: 16, {0x00, 0xFF}, Master, Slave1 ;
+    /// ```
+    ///
     /// The init_value specifies the signal value that shall be used by all subscriber nodes
     /// until the frame containing the signal is received. The init_value_scalar is used for
     /// scalar signals and the init_value_array is used for byte array signals. The initial_value for
@@ -70,6 +78,23 @@ pub struct LdfSignal {
     /// The subscribed_by specifies the node(s) that is subscribing to the signal.
     /// The subscribed_by identifiers shall exist in the node identifier set.
     pub subscribed_by: Vec<String>,
+
+    /// Location of the signal in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
+
+    /// Identifiers referenced by the signal (published_by, subscribed_by), with their location in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub references: Vec<LdfNamedSpan>,
+}
+
+impl ResolveSpans for LdfSignal {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+        self.references.resolve_spans(index);
+    }
 }
 
 /*
@@ -107,15 +132,16 @@ pub fn parse_ldf_signals(s: &str) -> IResult<&str, Vec<LdfSignal>> {
         // - May be any number of spaces before and after the subscribed_by node
         // - May be any number of spaces before and after the semicolon
         let (s, _) = skip_whitespace(remaining)?;
+        let entry = s;
         let (s, signal_name) = take_while(|c: char| c.is_alphanumeric() || c == '_')(s)?;
         let (s, _) = skip_whitespace(s)?;
         let (s, _) = tag(":")(s)?;
         let (s, _) = skip_whitespace(s)?;
-        let (s, signal_size) = take_while(|c: char| c.is_numeric())(s)?;
+        let (s, signal_size) = parse_integer(s)?;
         let (s, _) = skip_whitespace(s)?;
         let (s, _) = tag(",")(s)?;
         let (s, _) = skip_whitespace(s)?;
-        let (s, init_value) = take_while(|c: char| c.is_numeric())(s)?;
+        let (s, init_value) = parse_init_value(s)?;
         let (s, _) = skip_whitespace(s)?;
         let (s, _) = tag(",")(s)?;
         let (s, _) = skip_whitespace(s)?;
@@ -124,11 +150,13 @@ pub fn parse_ldf_signals(s: &str) -> IResult<&str, Vec<LdfSignal>> {
 
         let (s, symbol) = take_while(|c: char| c == ',' || c == ';')(s)?;
         let mut subscribed_by = Vec::new();
+        let mut references = vec![LdfNamedSpan::of(entry, published_by)];
         let s = match symbol {
             "," => {
                 // There is at least one subscribed_by node
                 let (s, subscribed_by_str) = take_until(";")(s)?;
                 subscribed_by = subscribed_by_str.split(',').map(|s| s.trim().to_string()).collect();
+                references.extend(list_spans(entry, subscribed_by_str));
                 let (s, _) = tag(";")(s)?;
                 s
             }
@@ -141,16 +169,19 @@ pub fn parse_ldf_signals(s: &str) -> IResult<&str, Vec<LdfSignal>> {
                 s
             }
         };
+        let span = LdfSpan::new(entry, s);
         let (s, _) = skip_whitespace(s)?;
 
         remaining = s;
 
         let signal = LdfSignal {
             name: signal_name.to_string(),
-            signal_size: signal_size.parse().unwrap(),
-            init_value: LdfSignalInitValue::Scalar(init_value.parse().unwrap()),
+            signal_size,
+            init_value,
             published_by: published_by.to_string(),
             subscribed_by,
+            span,
+            references,
         };
 
         signals.push(signal);
@@ -164,6 +195,32 @@ pub fn parse_ldf_signals(s: &str) -> IResult<&str, Vec<LdfSignal>> {
     Ok((s, signals))
 }
 
+fn parse_init_value(s: &str) -> IResult<&str, LdfSignalInitValue> {
+    // `0` or `0x1FF` or `{0x00, 0xFF}` or ...
+    // - May be any number of spaces before and after each byte of an array
+    if !s.starts_with('{') {
+        let (s, init_value) = parse_integer(s)?;
+        return Ok((s, LdfSignalInitValue::Scalar(init_value)));
+    }
+
+    let (s, _) = tag("{")(s)?;
+    let (rest, bytes) = take_until("}")(s)?;
+    let (rest, _) = tag("}")(rest)?;
+
+    let mut init_value = Vec::new();
+    for byte in bytes.split(',') {
+        let (_, byte) = parse_integer_token(byte)?;
+        init_value.push(byte);
+    }
+
+    // Byte arrays are at most 8 bytes long
+    if init_value.len() > 8 {
+        return Err(nom::Err::Error(LdfParseError::new(s, ErrorKind::TooLarge)));
+    }
+
+    Ok((rest, LdfSignalInitValue::Array(init_value)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -189,4 +246,45 @@ mod tests {
         assert_eq!(signals[0].subscribed_by, vec!["Slave1", "Slave2"]);
         assert_eq!(signals[1].name, "Signal2");
     }
+
+    #[test]
+    fn test_parse_ldf_signals_init_values() {
+        let input = r#"
+            Signals {
+                Scalar8: 8, 0xFF, Master, Slave1 ;
+                Scalar16: 16, 1023, Master, Slave1 ;
+                ScalarHex16: 16, 0xFFFF, Master ;
+                Array1: 8, {0x7F}, Slave1, Master ;
+                Array2: 16, { 0x00, 0xFF }, Master, Slave1 ;
+                Array8: 64, {0, 1, 2, 3, 4, 5, 6, 0xFF}, Master, Slave1 ;
+            }
+        "#;
+
+        let (_, signals) = parse_ldf_signals(input).unwrap();
+        assert_eq!(signals.len(), 6);
+        assert_eq!(signals[0].init_value, LdfSignalInitValue::Scalar(0xFF));
+        assert_eq!(signals[1].init_value, LdfSignalInitValue::Scalar(1023));
+        assert_eq!(signals[2].init_value, LdfSignalInitValue::Scalar(0xFFFF));
+        assert_eq!(signals[2].subscribed_by.len(), 0);
+        assert_eq!(signals[3].init_value, LdfSignalInitValue::Array(vec![0x7F]));
+        assert_eq!(signals[4].init_value, LdfSignalInitValue::Array(vec![0x00, 0xFF]));
+        assert_eq!(signals[4].published_by, "Master");
+        assert_eq!(signals[4].subscribed_by, vec!["Slave1"]);
+        assert_eq!(
+            signals[5].init_value,
+            LdfSignalInitValue::Array(vec![0, 1, 2, 3, 4, 5, 6, 0xFF])
+        );
+    }
+
+    #[test]
+    fn test_parse_ldf_signals_invalid_init_values() {
+        let too_large = "Signals { Signal1: 16, 0x10000, Master, Slave1 ; }";
+        assert!(parse_ldf_signals(too_large).is_err());
+
+        let byte_too_large = "Signals { Signal1: 16, {0x100, 0}, Master, Slave1 ; }";
+        assert!(parse_ldf_signals(byte_too_large).is_err());
+
+        let array_too_long = "Signals { Signal1: 64, {0, 0, 0, 0, 0, 0, 0, 0, 0}, Master, Slave1 ; }";
+        assert!(parse_ldf_signals(array_too_long).is_err());
+    }
 }
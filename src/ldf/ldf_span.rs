@@ -0,0 +1,162 @@
+/// Location of a parsed element in the LIN Description File (LDF)
+///
+/// Spans are only serialized if the `spans` feature is enabled, so that the default serde output
+/// stays the same.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub struct LdfSpan {
+    /// Byte offset of the first character of the element
+    pub start: usize,
+
+    /// Byte offset just after the last character of the element
+    pub end: usize,
+
+    /// Line of the first character of the element, starting at 1
+    pub line: usize,
+
+    /// Column of the first character of the element in characters, starting at 1
+    pub column: usize,
+}
+
+impl LdfSpan {
+    /// Span between two parser positions, given as the remaining input at each of them.
+    ///
+    /// The parsers only see the rest of the file, so the positions are stored as the distance to the
+    /// end of the file until they are resolved against the whole file with [`ResolveSpans`].
+    pub(crate) fn new(start: &str, end: &str) -> LdfSpan {
+        LdfSpan {
+            start: start.len(),
+            end: end.len(),
+            line: 0,
+            column: 0,
+        }
+    }
+
+    /// Span of `token`, a slice of the remaining input `s`
+    pub(crate) fn of(s: &str, token: &str) -> LdfSpan {
+        let start = &s[token.as_ptr() as usize - s.as_ptr() as usize..];
+        LdfSpan::new(start, &start[token.len()..])
+    }
+}
+
+/// Identifier with its location in the LIN Description File (LDF), e.g. a reference to a signal in a frame
+/// or the name of a section.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub struct LdfNamedSpan {
+    /// Identifier
+    pub name: String,
+
+    /// Location of the identifier (or the section)
+    pub span: LdfSpan,
+}
+
+impl LdfNamedSpan {
+    /// Named span of `token`, a slice of the remaining input `s`
+    pub(crate) fn of(s: &str, token: &str) -> LdfNamedSpan {
+        LdfNamedSpan {
+            name: token.to_string(),
+            span: LdfSpan::of(s, token),
+        }
+    }
+}
+
+/// Named spans of the comma separated identifiers in `list`, a slice of the remaining input `s`
+pub(crate) fn list_spans(s: &str, list: &str) -> Vec<LdfNamedSpan> {
+    list.split(',')
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .map(|item| LdfNamedSpan::of(s, item))
+        .collect()
+}
+
+/// Line and column lookup for the byte offsets of a file
+pub(crate) struct LineIndex<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(source: &'a str) -> LineIndex<'a> {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        LineIndex { source, line_starts }
+    }
+}
+
+/// Turns the spans of parsed elements (stored relative to the end of the file) into byte offsets, lines and columns
+pub(crate) trait ResolveSpans {
+    fn resolve_spans(&mut self, index: &LineIndex);
+}
+
+impl ResolveSpans for LdfSpan {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        let length = index.source.len();
+        self.start = length.saturating_sub(self.start);
+        self.end = length.saturating_sub(self.end);
+
+        let line = index
+            .line_starts
+            .partition_point(|line_start| *line_start <= self.start);
+        let line_start = index.line_starts[line - 1];
+        self.line = line;
+        self.column = index
+            .source
+            .get(line_start..self.start)
+            .map_or(0, |s| s.chars().count())
+            + 1;
+    }
+}
+
+impl ResolveSpans for LdfNamedSpan {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+    }
+}
+
+impl<T: ResolveSpans> ResolveSpans for Vec<T> {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.iter_mut().for_each(|item| item.resolve_spans(index));
+    }
+}
+
+impl<T: ResolveSpans> ResolveSpans for Option<T> {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        if let Some(item) = self {
+            item.resolve_spans(index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_spans() {
+        let source = "Signals {\n  Signal1: 8, 0, Master, Slave1, Slave2 ;\n}";
+        let s = &source[source.find("Signal1").unwrap()..];
+        let entry_end = &s[s.find(';').unwrap() + 1..];
+
+        let mut span = LdfSpan::new(s, entry_end);
+        let mut references = list_spans(s, &s[s.find("Master").unwrap()..s.find(';').unwrap()]);
+
+        let index = LineIndex::new(source);
+        span.resolve_spans(&index);
+        references.resolve_spans(&index);
+
+        assert_eq!(&source[span.start..span.end], "Signal1: 8, 0, Master, Slave1, Slave2 ;");
+        assert_eq!((span.line, span.column), (2, 3));
+
+        let names: Vec<&str> = references.iter().map(|reference| reference.name.as_str()).collect();
+        assert_eq!(names, vec!["Master", "Slave1", "Slave2"]);
+        let slave2 = references[2].span;
+        assert_eq!(&source[slave2.start..slave2.end], "Slave2");
+        assert_eq!((slave2.line, slave2.column), (2, 34));
+    }
+}
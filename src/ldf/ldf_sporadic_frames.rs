@@ -0,0 +1,165 @@
+use crate::ldf::ldf_comment::skip_whitespace;
+use crate::ldf::ldf_error::{tag, IResult};
+use crate::ldf::ldf_identifier::{parse_identifier, parse_identifier_list};
+use crate::ldf::ldf_span::{LdfNamedSpan, LdfSpan, LineIndex, ResolveSpans};
+
+/// `Sporadic_frames` section of a LIN Description File (LDF)
+/// ```text
+/// Sporadic_frames {
+///   SF_Lights: LightsHigh, LightsLow ;
+/// }
+/// ```
+/// ---
+/// Sporadic frame in the `Sporadic_frames` section of a LIN Description File (LDF)
+/// ```text
+/// SF_Lights: LightsHigh, LightsLow ;
+/// ```
+/// Reads as:
+/// ```text
+/// <sporadic_frame_name>: <frame_name> [, <frame_name>] ;
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub struct LdfSporadicFrame {
+    /// Sporadic frame name
+    pub name: String,
+
+    /// Unconditional frames associated with the sporadic frame.
+    /// They are listed in priority order, the first frame has the highest priority.
+    pub associated_frames: Vec<String>,
+
+    /// Location of the sporadic frame in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
+
+    /// Identifiers referenced by the sporadic frame (associated frames), with their location in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub references: Vec<LdfNamedSpan>,
+}
+
+impl ResolveSpans for LdfSporadicFrame {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+        self.references.resolve_spans(index);
+    }
+}
+
+/*
+Sporadic_frames {
+  SF_Lights: LightsHigh, LightsLow ;
+}
+*/
+
+pub fn parse_ldf_sporadic_frames(s: &str) -> IResult<&str, Vec<LdfSporadicFrame>> {
+    // `Sporadic_frames {` or `Sporadic_frames{` or ...
+    // - May be any number of spaces before and after the "Sporadic_frames" tag
+    // - May be any number of spaces before and after the opening curly brace
+    let (s, _) = skip_whitespace(s)?;
+
+    // Assume that if the section label is not present, then the section is not present.
+    if !s.starts_with("Sporadic_frames") {
+        return Ok((s, Vec::new()));
+    }
+
+    let (s, _) = tag("Sporadic_frames")(s)?;
+    let (s, _) = skip_whitespace(s)?;
+    let (s, _) = tag("{")(s)?;
+    let (s, _) = skip_whitespace(s)?;
+
+    let mut sporadic_frames = Vec::new();
+    let mut remaining = s;
+
+    while !remaining.starts_with('}') {
+        // `SF_Lights: LightsHigh, LightsLow ;` or `SF_Lights: LightsHigh, LightsLow;` or ...
+        // - May be any number of spaces before and after the sporadic frame name
+        // - May be any number of spaces before and after the colon
+        // - May be any number of spaces before and after each associated frame name
+        // - May be any number of spaces before and after the semicolon
+        let (s, _) = skip_whitespace(remaining)?;
+        let entry = s;
+        let (s, name) = parse_identifier(s)?;
+        let (s, _) = skip_whitespace(s)?;
+        let (s, _) = tag(":")(s)?;
+        let (s, _) = skip_whitespace(s)?;
+        let (s, associated_frames) = parse_identifier_list(s)?;
+        let (s, _) = tag(";")(s)?;
+        let span = LdfSpan::new(entry, s);
+        let (s, _) = skip_whitespace(s)?;
+
+        let references = associated_frames
+            .iter()
+            .map(|frame| LdfNamedSpan::of(entry, frame))
+            .collect();
+        let associated_frames = associated_frames.iter().map(|frame| frame.to_string()).collect();
+
+        sporadic_frames.push(LdfSporadicFrame {
+            name: name.to_string(),
+            associated_frames,
+            span,
+            references,
+        });
+
+        remaining = s;
+    }
+
+    let (remaining, _) = tag("}")(remaining)?;
+
+    Ok((remaining, sporadic_frames))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ldf_sporadic_frames() {
+        let input = r#"
+            Sporadic_frames {
+                SF_Lights: LightsHigh, LightsLow , LightsAux ;
+                SF_Horn:Horn;
+            }
+        "#;
+
+        let (_, sporadic_frames) = parse_ldf_sporadic_frames(input).unwrap();
+        assert_eq!(sporadic_frames.len(), 2);
+        assert_eq!(sporadic_frames[0].name, "SF_Lights");
+        assert_eq!(
+            sporadic_frames[0].associated_frames,
+            vec!["LightsHigh", "LightsLow", "LightsAux"]
+        );
+        assert_eq!(sporadic_frames[1].name, "SF_Horn");
+        assert_eq!(sporadic_frames[1].associated_frames, vec!["Horn"]);
+    }
+
+    #[test]
+    fn test_parse_ldf_sporadic_frames_comments() {
+        let input = r#"
+            Sporadic_frames {
+                SF_Lights: LightsHigh, // highest priority first
+                    LightsLow /* then the rest */ , LightsAux ;
+            }
+        "#;
+
+        let (_, sporadic_frames) = parse_ldf_sporadic_frames(input).unwrap();
+        assert_eq!(
+            sporadic_frames[0].associated_frames,
+            vec!["LightsHigh", "LightsLow", "LightsAux"]
+        );
+        let references: Vec<&str> = sporadic_frames[0]
+            .references
+            .iter()
+            .map(|reference| reference.name.as_str())
+            .collect();
+        assert_eq!(references, vec!["LightsHigh", "LightsLow", "LightsAux"]);
+
+        let input = r#"
+            Sporadic_frames {
+                SF_Lights: ;
+            }
+        "#;
+        assert!(parse_ldf_sporadic_frames(input).is_err());
+    }
+}
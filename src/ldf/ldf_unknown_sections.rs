@@ -0,0 +1,118 @@
+use crate::ldf::ldf_comment::skip_whitespace;
+use crate::ldf::ldf_error::{tag, IResult, LdfParseError};
+use crate::ldf::ldf_span::{LdfSpan, LineIndex, ResolveSpans};
+use nom::{bytes::complete::take_while1, error::ErrorKind};
+
+/// Section of a LIN Description File (LDF) that is not part of the specification
+/// (e.g. a tool or vendor specific extension). It is kept as-is instead of being parsed.
+/// ```text
+/// Vector_extensions {
+///   Setting1 = "Value" ;
+/// }
+/// ```
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub struct LdfUnknownSection {
+    /// Section keyword, e.g. `Vector_extensions`
+    pub name: String,
+
+    /// Raw text between the opening and the closing curly brace of the section
+    pub text: String,
+
+    /// Location of the section in the file
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub span: LdfSpan,
+}
+
+impl ResolveSpans for LdfUnknownSection {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.span.resolve_spans(index);
+    }
+}
+
+/*
+Vector_extensions {
+  Setting1 = "Value" ;
+  Nested { Setting2 = "}" ; }
+}
+*/
+
+pub fn parse_ldf_unknown_section(s: &str) -> IResult<&str, LdfUnknownSection> {
+    // `Vector_extensions {` or `Vector_extensions{` or ...
+    // - May be any number of spaces before and after the section name
+    // - May be any number of spaces before and after the opening curly brace
+    let (s, _) = skip_whitespace(s)?;
+    let entry = s;
+    let (s, name) = take_while1(|c: char| c.is_alphanumeric() || c == '_')(s)?;
+    let (s, _) = skip_whitespace(s)?;
+    let (s, _) = tag("{")(s)?;
+
+    // The body may contain nested curly braces, strings and comments.
+    // Braces inside of strings and comments do not count towards the nesting depth.
+    let mut depth = 0;
+    let mut chars = s.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => {
+                let section = LdfUnknownSection {
+                    name: name.to_string(),
+                    text: s[..i].to_string(),
+                    span: LdfSpan::new(entry, &s[i + 1..]),
+                };
+                return Ok((&s[i + 1..], section));
+            }
+            '}' => depth -= 1,
+            '"' => {
+                chars.find(|(_, c)| *c == '"');
+            }
+            '/' if chars.next_if(|(_, c)| *c == '/').is_some() => {
+                chars.find(|(_, c)| *c == '\n');
+            }
+            '/' if chars.next_if(|(_, c)| *c == '*').is_some() => {
+                while let Some((_, c)) = chars.next() {
+                    if c == '*' && chars.next_if(|(_, c)| *c == '/').is_some() {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // The closing curly brace is missing
+    Err(nom::Err::Error(LdfParseError::new(s, ErrorKind::TakeUntil)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ldf_unknown_section() {
+        let input = r#"
+            Vector_extensions {
+                Setting1 = "Value with } brace" ;
+                Nested { Setting2 = 1 ; } // Comment with } brace
+                /* Comment with { brace */
+            }
+            Nodes {
+        "#;
+
+        let (s, section) = parse_ldf_unknown_section(input).unwrap();
+        assert_eq!(section.name, "Vector_extensions");
+        assert!(section.text.contains(r#"Setting1 = "Value with } brace" ;"#));
+        assert!(section.text.contains("Nested { Setting2 = 1 ; }"));
+        assert!(section.text.trim_end().ends_with("/* Comment with { brace */"));
+        assert!(s.trim_start().starts_with("Nodes"));
+    }
+
+    #[test]
+    fn test_parse_ldf_unknown_section_unterminated() {
+        assert!(parse_ldf_unknown_section("Vector_extensions { Nested { }").is_err());
+        assert!(parse_ldf_unknown_section("Vector_extensions ;").is_err());
+    }
+}
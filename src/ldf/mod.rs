@@ -1,26 +1,47 @@
 pub mod ldf_comment;
+pub mod ldf_diagnostic;
 pub mod ldf_diagnostic_frames;
 pub mod ldf_diagnostic_signals;
+pub mod ldf_dynamic_frames;
+pub mod ldf_error;
+pub mod ldf_event_triggered_frames;
 pub mod ldf_frames;
 pub mod ldf_header;
+pub mod ldf_identifier;
 pub mod ldf_node_attributes;
+pub mod ldf_node_composition;
 pub mod ldf_nodes;
+pub mod ldf_number;
+pub mod ldf_recovery;
 pub mod ldf_schedule_tables;
 pub mod ldf_signal_encoding_types;
+pub mod ldf_signal_groups;
 pub mod ldf_signal_representation;
 pub mod ldf_signals;
+pub mod ldf_span;
+pub mod ldf_sporadic_frames;
+pub mod ldf_unknown_sections;
+pub mod validation;
 
-use crate::ldf::ldf_comment::skip_whitespace;
 use crate::ldf::ldf_diagnostic_frames::{parse_ldf_diagnostic_frames, LdfDiagnosticFrame};
 use crate::ldf::ldf_diagnostic_signals::{parse_ldf_diagnostic_signals, LdfDiagnosticSignal};
-use crate::ldf::ldf_frames::{parse_ldf_frames, LdfFrame};
+use crate::ldf::ldf_dynamic_frames::parse_ldf_dynamic_frames;
+use crate::ldf::ldf_error::LdfError;
+use crate::ldf::ldf_event_triggered_frames::{parse_ldf_event_triggered_frames, LdfEventTriggeredFrame};
+use crate::ldf::ldf_frames::{parse_ldf_frames, parse_ldf_lin13_frames, LdfFrame};
 use crate::ldf::ldf_header::{parse_ldf_header, LdfHeader};
 use crate::ldf::ldf_node_attributes::{parse_ldf_node_attributes, LdfNodeAttributes};
+use crate::ldf::ldf_node_composition::{parse_ldf_node_composition, LdfNodeComposition};
 use crate::ldf::ldf_nodes::{parse_ldf_nodes, LdfNodes};
+use crate::ldf::ldf_recovery::Recovery;
 use crate::ldf::ldf_schedule_tables::{parse_ldf_schedule_tables, LdfScheduleTable};
 use crate::ldf::ldf_signal_encoding_types::{parse_ldf_signal_encoding_types, LdfSignalEncodingType};
+use crate::ldf::ldf_signal_groups::{parse_ldf_signal_groups, LdfSignalGroup};
 use crate::ldf::ldf_signal_representation::{parse_ldf_signal_representation, LdfSignalRepresentation};
 use crate::ldf::ldf_signals::{parse_ldf_signals, LdfSignal};
+use crate::ldf::ldf_span::{LdfNamedSpan, LineIndex, ResolveSpans};
+use crate::ldf::ldf_sporadic_frames::{parse_ldf_sporadic_frames, LdfSporadicFrame};
+use crate::ldf::ldf_unknown_sections::{parse_ldf_unknown_section, LdfUnknownSection};
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -28,14 +49,26 @@ use crate::ldf::ldf_signals::{parse_ldf_signals, LdfSignal};
 pub struct LinLdf {
     pub header: LdfHeader,
     pub nodes: LdfNodes,
+    pub node_compositions: Vec<LdfNodeComposition>,
     pub signals: Vec<LdfSignal>,
     pub diagnostic_signals: Vec<LdfDiagnosticSignal>,
     pub frames: Vec<LdfFrame>,
+    pub sporadic_frames: Vec<LdfSporadicFrame>,
+    /// Frame identifiers reserved for dynamic frames (LIN 1.3 only)
+    pub dynamic_frames: Vec<u8>,
+    pub event_triggered_frames: Vec<LdfEventTriggeredFrame>,
     pub diagnostic_frames: Vec<LdfDiagnosticFrame>,
     pub node_attributes: Vec<LdfNodeAttributes>,
     pub schedule_tables: Vec<LdfScheduleTable>,
+    pub signal_groups: Vec<LdfSignalGroup>,
     pub signal_encoding_types: Vec<LdfSignalEncodingType>,
     pub signal_representations: Vec<LdfSignalRepresentation>,
+    /// Sections that are not part of the specification (e.g. vendor specific extensions), in file order
+    pub unknown_sections: Vec<LdfUnknownSection>,
+    /// Location of the header (named `header`) and of each section, in file order
+    #[cfg_attr(all(feature = "serde", not(feature = "spans")), serde(skip))]
+    #[cfg_attr(all(feature = "ts-rs", not(feature = "spans")), ts(skip))]
+    pub section_spans: Vec<LdfNamedSpan>,
 }
 
 impl LinLdf {
@@ -60,125 +93,198 @@ impl LinLdf {
     /// (<Signal_encoding_type_def>)
     /// (<Signal_representation_def>)
     /// ```
-    pub fn parse(s: &str) -> Result<LinLdf, &'static str> {
-        // Header
-        let (s, _) = skip_whitespace(s).map_err(|e| {
-            eprintln!("Failed to skip whitespace and comments: {}", e.to_string());
-            "Failed to skip whitespace and comments"
-        })?;
-        let (s, header) = parse_ldf_header(s).map_err(|e| {
-            eprintln!("Failed to parse header: {}", e);
-            "Failed to parse header"
-        })?;
+    ///
+    /// LIN 1.3 files (selected by `LIN_protocol_version`) follow the same structure, except that
+    /// the frame size in `Frames` is optional, `Node_attributes` is optional and a
+    /// `(<Dynamic_frame_def>)` section may follow the sporadic frames.
+    ///
+    /// The header must come first, but the sections after it may appear in any order. Each section
+    /// may appear at most once. Sections that are not part of the specification (e.g. vendor
+    /// specific extensions) are kept in `unknown_sections` instead of failing the parse.
+    ///
+    /// Every section, entry and identifier reference records its location in the file (its `span`,
+    /// `references` and `section_spans` fields). They are only serialized with the `spans` feature.
+    pub fn parse(source: &str) -> Result<LinLdf, LdfError> {
+        let (ldf, errors) = LinLdf::parse_with_recovery(source);
+        match errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(ldf),
+        }
+    }
 
-        // Nodes
-        let (s, _) = skip_whitespace(s).map_err(|e| {
-            eprintln!("Failed to skip whitespace and comments: {}", e);
-            "Failed to skip whitespace and comments"
-        })?;
-        let (s, nodes) = parse_ldf_nodes(s).map_err(|e| {
-            eprintln!("Failed to parse Nodes section: {}", e);
-            "Failed to parse Nodes section"
-        })?;
+    /// Same as [`LinLdf::parse`], but does not stop at the first error.
+    ///
+    /// After an error, parsing skips to the next `;` or the matching `}` and continues with the
+    /// remaining entries and sections. Returns what could be parsed, together with all errors
+    /// (empty if the file is valid). Sections that could not be parsed at all are left empty.
+    pub fn parse_with_recovery(source: &str) -> (LinLdf, Vec<LdfError>) {
+        let mut recovery = Recovery::new(source);
 
-        // Signals
-        let (s, _) = skip_whitespace(s).map_err(|e| {
-            eprintln!("Failed to skip whitespace and comments: {}", e);
-            "Failed to skip whitespace and comments"
-        })?;
-        let (s, signals) = parse_ldf_signals(s).map_err(|e| {
-            eprintln!("Failed to parse Signals section: {}", e);
-            "Failed to parse Signals section"
-        })?;
+        // Header
+        let header = recovery
+            .parse_statements("header", parse_ldf_header)
+            .unwrap_or_default();
+        let lin13 = header.is_lin13();
+        let parse_frames = if lin13 {
+            parse_ldf_lin13_frames
+        } else {
+            parse_ldf_frames
+        };
 
-        // Diagnostic signals
-        let (s, _) = skip_whitespace(s).map_err(|e| {
-            eprintln!("Failed to skip whitespace and comments: {}", e);
-            "Failed to skip whitespace and comments"
-        })?;
-        let (s, diagnostic_signals) = parse_ldf_diagnostic_signals(s).map_err(|e| {
-            eprintln!("Failed to parse Diagnostic_signals section: {}", e);
-            "Failed to parse Diagnostic_signals section"
-        })?;
+        let mut nodes = None;
+        let mut node_compositions = None;
+        let mut signals = None;
+        let mut diagnostic_signals = None;
+        let mut frames = None;
+        let mut sporadic_frames = None;
+        let mut dynamic_frames = None;
+        let mut event_triggered_frames = None;
+        let mut diagnostic_frames = None;
+        let mut node_attributes = None;
+        let mut schedule_tables = None;
+        let mut signal_groups = None;
+        let mut signal_encoding_types = None;
+        let mut signal_representations = None;
+        let mut unknown_sections = Vec::new();
 
-        // Frames
-        let (s, _) = skip_whitespace(s).map_err(|e| {
-            eprintln!("Failed to skip whitespace and comments: {}", e);
-            "Failed to skip whitespace and comments"
-        })?;
-        let (s, frames) = parse_ldf_frames(s).map_err(|e| {
-            eprintln!("Failed to parse Frames section: {}", e);
-            "Failed to parse Frames section"
-        })?;
+        // Sections, dispatched on their keyword
+        let mut parsed_sections: Vec<String> = Vec::new();
+        loop {
+            recovery.skip_whitespace();
+            if recovery.remaining().is_empty() {
+                break;
+            }
 
-        // Diagnostic frames
-        let (s, _) = skip_whitespace(s).map_err(|e| {
-            eprintln!("Failed to skip whitespace and comments: {}", e);
-            "Failed to skip whitespace and comments"
-        })?;
-        let (s, diagnostic_frames) = parse_ldf_diagnostic_frames(s).map_err(|e| {
-            eprintln!("Failed to parse Diagnostic_frames section: {}", e);
-            "Failed to parse Diagnostic_frames section"
-        })?;
+            let remaining = recovery.remaining();
+            let keyword_length = remaining
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(remaining.len());
+            let keyword = remaining[..keyword_length].to_string();
+            let keyword = match keyword.is_empty() {
+                true => "unknown",
+                false => keyword.as_str(),
+            };
 
-        // Node attributes
-        let (s, _) = skip_whitespace(s).map_err(|e| {
-            eprintln!("Failed to skip whitespace and comments: {}", e);
-            "Failed to skip whitespace and comments"
-        })?;
-        let (s, node_attributes) = parse_ldf_node_attributes(s).map_err(|e| {
-            eprintln!("Failed to parse Node_attributes section: {}", e);
-            "Failed to parse Node_attributes section"
-        })?;
+            // Unknown sections are not checked for duplicates, vendors may repeat them
+            if KNOWN_SECTIONS.contains(&keyword) && parsed_sections.iter().any(|section| section == keyword) {
+                let message = format!("duplicate {} section", keyword);
+                recovery.error_at(recovery.offset(), keyword, None, message);
+                recovery.skip_section(recovery.offset());
+                continue;
+            }
+            parsed_sections.push(keyword.to_string());
 
-        // Schedule tables
-        let (s, _) = skip_whitespace(s).map_err(|e| {
-            eprintln!("Failed to skip whitespace and comments: {}", e);
-            "Failed to skip whitespace and comments"
-        })?;
-        let (s, schedule_tables) = parse_ldf_schedule_tables(s).map_err(|e| {
-            eprintln!("Failed to parse Schedule_tables section: {}", e);
-            "Failed to parse Schedule_tables section"
-        })?;
+            match keyword {
+                "Nodes" => nodes = recovery.parse_section(keyword, parse_ldf_nodes),
+                "Node_composition" => node_compositions = recovery.parse_section(keyword, parse_ldf_node_composition),
+                "Signals" => signals = recovery.parse_section(keyword, parse_ldf_signals),
+                "Diagnostic_signals" => {
+                    diagnostic_signals = recovery.parse_section(keyword, parse_ldf_diagnostic_signals)
+                }
+                "Frames" => frames = recovery.parse_section(keyword, parse_frames),
+                "Sporadic_frames" => sporadic_frames = recovery.parse_section(keyword, parse_ldf_sporadic_frames),
+                "Dynamic_frames" => dynamic_frames = recovery.parse_section(keyword, parse_ldf_dynamic_frames),
+                "Event_triggered_frames" => {
+                    event_triggered_frames = recovery.parse_section(keyword, parse_ldf_event_triggered_frames)
+                }
+                "Diagnostic_frames" => diagnostic_frames = recovery.parse_section(keyword, parse_ldf_diagnostic_frames),
+                "Node_attributes" => node_attributes = recovery.parse_section(keyword, parse_ldf_node_attributes),
+                "Schedule_tables" => schedule_tables = recovery.parse_section(keyword, parse_ldf_schedule_tables),
+                "Signal_groups" => signal_groups = recovery.parse_section(keyword, parse_ldf_signal_groups),
+                "Signal_encoding_types" => {
+                    signal_encoding_types = recovery.parse_section(keyword, parse_ldf_signal_encoding_types)
+                }
+                "Signal_representation" => {
+                    signal_representations = recovery.parse_section(keyword, parse_ldf_signal_representation)
+                }
+                _ => {
+                    if let Some(section) = recovery.parse_section(keyword, parse_ldf_unknown_section) {
+                        unknown_sections.push(section);
+                    }
+                }
+            }
+        }
 
-        // Signal encoding types
-        let (s, _) = skip_whitespace(s).map_err(|e| {
-            eprintln!("Failed to skip whitespace and comments: {}", e);
-            "Failed to skip whitespace and comments"
-        })?;
-        let (s, signal_encoding_types) = parse_ldf_signal_encoding_types(s).map_err(|e| {
-            eprintln!("Failed to parse Signal_encoding_types section: {}", e);
-            "Failed to parse Signal_encoding_types section"
-        })?;
+        // Required sections
+        let mut required_sections = vec!["Nodes", "Signals", "Frames", "Schedule_tables"];
+        if !lin13 {
+            // Node attributes are not part of LIN 1.3
+            required_sections.push("Node_attributes");
+        }
+        for section in required_sections {
+            if !parsed_sections.iter().any(|parsed_section| parsed_section == section) {
+                let message = format!("missing {} section", section);
+                recovery.error_at(source.len(), section, Some(section), message);
+            }
+        }
 
-        // Signal representations
-        let (s, _) = skip_whitespace(s).map_err(|e| {
-            eprintln!("Failed to skip whitespace and comments: {}", e);
-            "Failed to skip whitespace and comments"
-        })?;
-        let (_, signal_representations) = parse_ldf_signal_representation(s).map_err(|e| {
-            eprintln!("Failed to parse Signal_representation section: {}", e);
-            "Failed to parse Signal_representation section"
-        })?;
-
-        Ok(LinLdf {
+        let mut ldf = LinLdf {
             header,
-            nodes,
-            signals,
-            diagnostic_signals,
-            frames,
-            diagnostic_frames,
-            node_attributes,
-            schedule_tables,
-            signal_encoding_types,
-            signal_representations,
-        })
+            nodes: nodes.unwrap_or_default(),
+            node_compositions: node_compositions.unwrap_or_default(),
+            signals: signals.unwrap_or_default(),
+            diagnostic_signals: diagnostic_signals.unwrap_or_default(),
+            frames: frames.unwrap_or_default(),
+            sporadic_frames: sporadic_frames.unwrap_or_default(),
+            dynamic_frames: dynamic_frames.unwrap_or_default(),
+            event_triggered_frames: event_triggered_frames.unwrap_or_default(),
+            diagnostic_frames: diagnostic_frames.unwrap_or_default(),
+            node_attributes: node_attributes.unwrap_or_default(),
+            schedule_tables: schedule_tables.unwrap_or_default(),
+            signal_groups: signal_groups.unwrap_or_default(),
+            signal_encoding_types: signal_encoding_types.unwrap_or_default(),
+            signal_representations: signal_representations.unwrap_or_default(),
+            unknown_sections,
+            section_spans: recovery.section_spans,
+        };
+        ldf.resolve_spans(&LineIndex::new(source));
+        (ldf, recovery.errors)
+    }
+}
+
+impl ResolveSpans for LinLdf {
+    fn resolve_spans(&mut self, index: &LineIndex) {
+        self.nodes.resolve_spans(index);
+        self.node_compositions.resolve_spans(index);
+        self.signals.resolve_spans(index);
+        self.diagnostic_signals.resolve_spans(index);
+        self.frames.resolve_spans(index);
+        self.sporadic_frames.resolve_spans(index);
+        self.event_triggered_frames.resolve_spans(index);
+        self.diagnostic_frames.resolve_spans(index);
+        self.node_attributes.resolve_spans(index);
+        self.schedule_tables.resolve_spans(index);
+        self.signal_groups.resolve_spans(index);
+        self.signal_encoding_types.resolve_spans(index);
+        self.signal_representations.resolve_spans(index);
+        self.unknown_sections.resolve_spans(index);
+        self.section_spans.resolve_spans(index);
     }
 }
 
+/// Keywords of the sections defined by the specification (the header excluded)
+const KNOWN_SECTIONS: [&str; 14] = [
+    "Nodes",
+    "Node_composition",
+    "Signals",
+    "Diagnostic_signals",
+    "Frames",
+    "Sporadic_frames",
+    "Dynamic_frames",
+    "Event_triggered_frames",
+    "Diagnostic_frames",
+    "Node_attributes",
+    "Schedule_tables",
+    "Signal_groups",
+    "Signal_encoding_types",
+    "Signal_representation",
+];
+
 #[cfg(test)]
 mod tests {
+    use crate::ldf::ldf_schedule_tables::LdfScheduleCommand;
     use crate::ldf::ldf_signals::LdfSignalInitValue;
+    use crate::ldf::ldf_span::LdfSpan;
 
     use super::*;
 
@@ -197,6 +303,12 @@ mod tests {
                 Slaves: Slave1, Slave2, Slave3 ;
             }
 
+            Node_composition {
+                configuration Cfg1 {
+                    Composite1 { Slave1, Slave2 } ;
+                }
+            }
+
             // MY LINE COMMENT
 
             Signals {
@@ -228,6 +340,14 @@ mod tests {
                 }
             }
 
+            Sporadic_frames {
+                SF1: Frame1, Frame2 ;
+            }
+
+            Event_triggered_frames {
+                ETF1: AllFrames, 0x3A, Frame2 ;
+            }
+
             Diagnostic_frames {
                 MasterReq: 0x3C {
                     MasterReqB0, 0 ;
@@ -291,6 +411,13 @@ mod tests {
                 }
             }
 
+            Signal_groups {
+                Group1: 20 {
+                    Signal1, 0 ;
+                    Signal2, 10 ;
+                }
+            }
+
             Signal_encoding_types {
                 ENC_BOOL {
                     logical_value, 0, "FALSE" ;
@@ -321,12 +448,23 @@ mod tests {
         // Nodes
         assert_eq!(ldf.nodes.master.name, "Master");
         assert_eq!(ldf.nodes.master.time_base, "5 ms");
+        assert_eq!(ldf.nodes.master.time_base_ms, 5.0);
         assert_eq!(ldf.nodes.master.jitter, "0.1 ms");
         assert_eq!(ldf.nodes.slaves.len(), 3);
         assert_eq!(ldf.nodes.slaves[0].name, "Slave1");
         assert_eq!(ldf.nodes.slaves[1].name, "Slave2");
         assert_eq!(ldf.nodes.slaves[2].name, "Slave3");
 
+        // Node composition
+        assert_eq!(ldf.node_compositions.len(), 1);
+        assert_eq!(ldf.node_compositions[0].configuration_name, "Cfg1");
+        assert_eq!(ldf.node_compositions[0].composite_nodes.len(), 1);
+        assert_eq!(ldf.node_compositions[0].composite_nodes[0].name, "Composite1");
+        assert_eq!(
+            ldf.node_compositions[0].composite_nodes[0].logical_nodes,
+            vec!["Slave1", "Slave2"]
+        );
+
         // Signals
         assert_eq!(ldf.signals.len(), 6);
         assert_eq!(ldf.signals[0].name, "Signal1");
@@ -370,6 +508,21 @@ mod tests {
         assert_eq!(ldf.frames[1].signals[1].signal_name, "Signal4");
         assert_eq!(ldf.frames[1].signals[1].start_bit, 10);
 
+        // Sporadic frames
+        assert_eq!(ldf.sporadic_frames.len(), 1);
+        assert_eq!(ldf.sporadic_frames[0].name, "SF1");
+        assert_eq!(ldf.sporadic_frames[0].associated_frames, vec!["Frame1", "Frame2"]);
+
+        // Event triggered frames
+        assert_eq!(ldf.event_triggered_frames.len(), 1);
+        assert_eq!(ldf.event_triggered_frames[0].frame_name, "ETF1");
+        assert_eq!(ldf.event_triggered_frames[0].frame_id, 0x3A);
+        assert_eq!(
+            ldf.event_triggered_frames[0].collision_resolving_schedule_table,
+            Some("AllFrames".to_string())
+        );
+        assert_eq!(ldf.event_triggered_frames[0].associated_frames, vec!["Frame2"]);
+
         // Diagnostic frames
         assert_eq!(ldf.diagnostic_frames.len(), 2);
         assert_eq!(ldf.diagnostic_frames[0].frame_name, "MasterReq");
@@ -392,43 +545,60 @@ mod tests {
         assert_eq!(ldf.node_attributes[0].node_name, "Slave1");
         assert_eq!(ldf.node_attributes[0].lin_protocol, "2.1");
         assert_eq!(ldf.node_attributes[0].configured_nad, 0xB);
-        assert_eq!(ldf.node_attributes[0].initial_nad, 0xB);
+        assert_eq!(ldf.node_attributes[0].initial_nad, Some(0xB));
         assert_eq!(ldf.node_attributes[0].supplier_id, 0x123);
         assert_eq!(ldf.node_attributes[0].function_id, 0x4567);
-        assert_eq!(ldf.node_attributes[0].variant, 8);
-        assert_eq!(ldf.node_attributes[0].response_error, "Signal1");
-        assert_eq!(ldf.node_attributes[0].p2_min, "100 ms");
-        assert_eq!(ldf.node_attributes[0].st_min, "0 ms");
-        assert_eq!(ldf.node_attributes[0].n_as_timeout, "1000 ms");
-        assert_eq!(ldf.node_attributes[0].n_cr_timeout, "1000 ms");
+        assert_eq!(ldf.node_attributes[0].variant, Some(8));
+        assert_eq!(ldf.node_attributes[0].response_error.as_deref(), Some("Signal1"));
+        assert_eq!(ldf.node_attributes[0].p2_min.as_deref(), Some("100 ms"));
+        assert_eq!(ldf.node_attributes[0].st_min.as_deref(), Some("0 ms"));
+        assert_eq!(ldf.node_attributes[0].n_as_timeout.as_deref(), Some("1000 ms"));
+        assert_eq!(ldf.node_attributes[0].n_cr_timeout.as_deref(), Some("1000 ms"));
         assert_eq!(ldf.node_attributes[0].configurable_frames.len(), 2);
-        assert_eq!(ldf.node_attributes[0].configurable_frames[0], "Frame1");
-        assert_eq!(ldf.node_attributes[0].configurable_frames[1], "Frame2");
+        assert_eq!(ldf.node_attributes[0].configurable_frames[0].frame_name, "Frame1");
+        assert_eq!(ldf.node_attributes[0].configurable_frames[1].frame_name, "Frame2");
         assert_eq!(ldf.node_attributes[1].node_name, "Slave2");
         assert_eq!(ldf.node_attributes[1].lin_protocol, "2.1");
         assert_eq!(ldf.node_attributes[1].configured_nad, 0xC);
-        assert_eq!(ldf.node_attributes[1].initial_nad, 0xC);
+        assert_eq!(ldf.node_attributes[1].initial_nad, Some(0xC));
         assert_eq!(ldf.node_attributes[1].supplier_id, 0x124);
         assert_eq!(ldf.node_attributes[1].function_id, 0x4568);
-        assert_eq!(ldf.node_attributes[1].variant, 0x66);
-        assert_eq!(ldf.node_attributes[1].response_error, "Signal2");
-        assert_eq!(ldf.node_attributes[1].p2_min, "100 ms");
-        assert_eq!(ldf.node_attributes[1].st_min, "0 ms");
-        assert_eq!(ldf.node_attributes[1].n_as_timeout, "1000 ms");
-        assert_eq!(ldf.node_attributes[1].n_cr_timeout, "1000 ms");
+        assert_eq!(ldf.node_attributes[1].variant, Some(0x66));
+        assert_eq!(ldf.node_attributes[1].response_error.as_deref(), Some("Signal2"));
+        assert_eq!(ldf.node_attributes[1].p2_min.as_deref(), Some("100 ms"));
+        assert_eq!(ldf.node_attributes[1].st_min.as_deref(), Some("0 ms"));
+        assert_eq!(ldf.node_attributes[1].n_as_timeout.as_deref(), Some("1000 ms"));
+        assert_eq!(ldf.node_attributes[1].n_cr_timeout.as_deref(), Some("1000 ms"));
         assert_eq!(ldf.node_attributes[1].configurable_frames.len(), 2);
-        assert_eq!(ldf.node_attributes[1].configurable_frames[0], "Frame1");
-        assert_eq!(ldf.node_attributes[1].configurable_frames[1], "Frame2");
+        assert_eq!(ldf.node_attributes[1].configurable_frames[0].frame_name, "Frame1");
+        assert_eq!(ldf.node_attributes[1].configurable_frames[1].frame_name, "Frame2");
 
         // Schedule tables
         assert_eq!(ldf.schedule_tables.len(), 1);
         assert_eq!(ldf.schedule_tables[0].schedule_table_name, "AllFrames");
         assert_eq!(ldf.schedule_tables[0].frame_delays.len(), 2);
-        assert_eq!(ldf.schedule_tables[0].frame_delays[0].frame_name, "Frame1");
+        assert_eq!(
+            ldf.schedule_tables[0].frame_delays[0].command,
+            LdfScheduleCommand::Frame {
+                frame_name: "Frame1".to_string()
+            }
+        );
         assert_eq!(ldf.schedule_tables[0].frame_delays[0].frame_time, 10.0);
-        assert_eq!(ldf.schedule_tables[0].frame_delays[1].frame_name, "Frame2");
+        assert_eq!(
+            ldf.schedule_tables[0].frame_delays[1].command,
+            LdfScheduleCommand::Frame {
+                frame_name: "Frame2".to_string()
+            }
+        );
         assert_eq!(ldf.schedule_tables[0].frame_delays[1].frame_time, 10.0);
 
+        // Signal groups
+        assert_eq!(ldf.signal_groups.len(), 1);
+        assert_eq!(ldf.signal_groups[0].name, "Group1");
+        assert_eq!(ldf.signal_groups[0].group_size, 20);
+        assert_eq!(ldf.signal_groups[0].signals.len(), 2);
+        assert_eq!(ldf.signal_groups[0].signals[1], ("Signal2".to_string(), 10));
+
         // Signal encoding types
         assert_eq!(ldf.signal_encoding_types.len(), 3);
         assert_eq!(ldf.signal_encoding_types[0].encoding_type_name, "ENC_BOOL");
@@ -441,4 +611,565 @@ mod tests {
         assert_eq!(ldf.signal_representations[0].signal_names[0], "Signal1");
         assert_eq!(ldf.signal_representations[0].signal_names[1], "Signal2");
     }
+
+    #[test]
+    fn test_parse_lin13() {
+        let input = r#"
+            LIN_description_file ;
+            LIN_protocol_version = "1.3" ;
+            LIN_language_version = "1.3" ;
+            LIN_speed = 9.6 kbps ;
+
+            Nodes {
+                Master: CEM, 5 ms, 0.1 ms ;
+                Slaves: LSM, RSM ;
+            }
+
+            Signals {
+                LightOn: 1, 0, CEM, LSM, RSM ;
+                LeftFault: 1, 0, LSM, CEM ;
+                RightFault: 1, 0, RSM, CEM ;
+            }
+
+            Frames {
+                CEM_Frm1: 0x01, CEM {
+                    LightOn, 0 ;
+                }
+                LSM_Frm1: 0x22, LSM, 2 {
+                    LeftFault, 0 ;
+                }
+                RSM_Frm1: 0x33, RSM {
+                    RightFault, 0 ;
+                }
+            }
+
+            Dynamic_frames {
+                0x38, 0x39 ;
+            }
+
+            Schedule_tables {
+                MySchedule1 {
+                    CEM_Frm1 delay 15 ms ;
+                    LSM_Frm1 delay 15 ms ;
+                    RSM_Frm1 delay 20 ms ;
+                }
+            }
+        "#;
+
+        let ldf = LinLdf::parse(input).unwrap();
+
+        assert!(ldf.header.is_lin13());
+        assert_eq!(ldf.header.lin_speed, 9600);
+        assert_eq!(ldf.nodes.master.name, "CEM");
+        assert_eq!(ldf.signals.len(), 3);
+
+        assert_eq!(ldf.frames.len(), 3);
+        assert_eq!(ldf.frames[0].frame_size, 2);
+        assert_eq!(ldf.frames[1].frame_size, 2);
+        assert_eq!(ldf.frames[2].frame_size, 8);
+
+        assert_eq!(ldf.dynamic_frames, vec![0x38, 0x39]);
+        assert!(ldf.node_attributes.is_empty());
+        assert_eq!(ldf.schedule_tables.len(), 1);
+        assert_eq!(ldf.schedule_tables[0].frame_delays.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_sections_in_any_order() {
+        let input = r#"
+            LIN_description_file ;
+            LIN_protocol_version = "2.1" ;
+            LIN_language_version = "2.1" ;
+            LIN_speed = 19.2 kbps ;
+
+            Vector_extensions {
+                Setting1 = "Value" ;
+                Nested { Setting2 = 1 ; }
+            }
+
+            Signal_encoding_types {
+                ENC_BOOL {
+                    logical_value, 0, "FALSE" ;
+                }
+            }
+
+            Schedule_tables {
+                Table1 {
+                    Frame1 delay 10 ms ;
+                }
+            }
+
+            Node_attributes {
+                Slave1 {
+                    LIN_protocol = "2.1" ;
+                    configured_NAD = 0x01 ;
+                    product_id = 0x1234, 0x5678 ;
+                }
+            }
+
+            Frames {
+                Frame1: 0x10, Master, 1 {
+                    Signal1, 0 ;
+                }
+            }
+
+            Signals {
+                Signal1: 1, 0, Master, Slave1 ;
+            }
+
+            Nodes {
+                Master: Master, 5 ms, 0.1 ms ;
+                Slaves: Slave1 ;
+            }
+
+            Tool_settings { }
+        "#;
+
+        let ldf = LinLdf::parse(input).unwrap();
+        assert_eq!(ldf.nodes.master.name, "Master");
+        assert_eq!(ldf.signals.len(), 1);
+        assert_eq!(ldf.frames.len(), 1);
+        assert_eq!(ldf.node_attributes.len(), 1);
+        assert_eq!(ldf.schedule_tables.len(), 1);
+        assert_eq!(ldf.signal_encoding_types.len(), 1);
+
+        assert_eq!(ldf.unknown_sections.len(), 2);
+        assert_eq!(ldf.unknown_sections[0].name, "Vector_extensions");
+        assert!(ldf.unknown_sections[0].text.contains("Nested { Setting2 = 1 ; }"));
+        assert_eq!(ldf.unknown_sections[1].name, "Tool_settings");
+        assert_eq!(ldf.unknown_sections[1].text, " ");
+    }
+
+    #[test]
+    fn test_parse_duplicate_and_missing_sections() {
+        let header = r#"
+            LIN_description_file ;
+            LIN_protocol_version = "2.1" ;
+            LIN_language_version = "2.1" ;
+            LIN_speed = 19.2 kbps ;
+        "#;
+        let nodes = "Nodes { Master: Master, 5 ms, 0.1 ms ; Slaves: Slave1 ; }";
+        let signals = "Signals { Signal1: 1, 0, Master, Slave1 ; }";
+        let frames = "Frames { Frame1: 0x10, Master, 1 { Signal1, 0 ; } }";
+        let node_attributes = r#"Node_attributes {
+            Slave1 { LIN_protocol = "2.1" ; configured_NAD = 0x01 ; product_id = 0x1234, 0x5678 ; }
+        }"#;
+        let schedule_tables = "Schedule_tables { Table1 { Frame1 delay 10 ms ; } }";
+
+        let complete = [header, nodes, signals, frames, node_attributes, schedule_tables].join("\n");
+        assert!(LinLdf::parse(&complete).is_ok());
+
+        let duplicate = [
+            header,
+            nodes,
+            signals,
+            frames,
+            signals,
+            node_attributes,
+            schedule_tables,
+        ]
+        .join("\n");
+        let error = LinLdf::parse(&duplicate).err().unwrap();
+        assert_eq!(error.section, "Signals");
+        assert_eq!(error.message, "duplicate Signals section");
+        assert_eq!(error.snippet, signals);
+
+        let missing = [header, nodes, signals, node_attributes, schedule_tables].join("\n");
+        let error = LinLdf::parse(&missing).err().unwrap();
+        assert_eq!(error.section, "Frames");
+        assert_eq!(error.message, "missing Frames section");
+
+        let unterminated = [
+            header,
+            nodes,
+            signals,
+            frames,
+            node_attributes,
+            schedule_tables,
+            "Vendor {",
+        ]
+        .join("\n");
+        let error = LinLdf::parse(&unterminated).err().unwrap();
+        assert_eq!(error.section, "Vendor");
+        assert_eq!(error.snippet, "Vendor {");
+    }
+
+    #[test]
+    fn test_parse_error_location() {
+        let input = r#"LIN_description_file ;
+LIN_protocol_version = "2.1" ;
+LIN_language_version = "2.1" ;
+LIN_speed = 19.2 kbps ;
+
+Nodes {
+    Master: Master, 5 ms, 0.1 ms ;
+    Slaves: Slave1 ;
+}
+
+Signals {
+    Signal1: 1, 0, Master, Slave1 ;
+}
+
+Frames {
+    Frame1: 0x10, Master, 1 {
+        Signal1 0 ;
+    }
+}
+"#;
+
+        let error = LinLdf::parse(input).err().unwrap();
+        assert_eq!(error.line, 17);
+        assert_eq!(error.column, 17);
+        assert_eq!(error.offset, input.find("0 ;").unwrap());
+        assert_eq!(error.section, "Frames");
+        assert_eq!(error.expected.as_deref(), Some(","));
+        assert_eq!(error.snippet, "        Signal1 0 ;");
+        assert_eq!(
+            error.to_string().lines().take(2).collect::<Vec<_>>(),
+            vec!["error: expected `,`", "  --> line 17, column 17 (Frames section)"]
+        );
+    }
+
+    #[test]
+    fn test_parse_with_recovery() {
+        let input = r#"LIN_description_file ;
+LIN_protocol_version = "2.1" ;
+LIN_language_version = "2.1" ;
+LIN_speed = 19.2 kbps ;
+
+Nodes {
+    Master: Master, 5 ms, 0.1 ms ;
+    Slaves: Slave1 ;
+}
+
+Signals {
+    Signal1: 1, 0, Master, Slave1 ;
+    Signal2: 1, 0x1FF Master, Slave1 ;
+    Signal3: 1, 0, Master, Slave1 ;
+}
+
+Frames {
+    Frame1: 0x1FF, Master, 1 {
+        Signal1, 0 ;
+    }
+    Frame2: 0x11, Master, 1 {
+        Signal3 0 ;
+        Signal1, 0 ;
+    }
+}
+
+Vendor_extension ;
+
+Schedule_tables {
+    Table1 {
+        Frame2 delay 10 ms ;
+    }
+}
+"#;
+
+        let (ldf, errors) = LinLdf::parse_with_recovery(input);
+
+        // Best-effort result without the broken entries
+        assert_eq!(ldf.nodes.master.name, "Master");
+        let signals: Vec<&str> = ldf.signals.iter().map(|signal| signal.name.as_str()).collect();
+        assert_eq!(signals, vec!["Signal1", "Signal3"]);
+        assert_eq!(ldf.frames.len(), 1);
+        assert_eq!(ldf.frames[0].frame_name, "Frame2");
+        assert_eq!(ldf.frames[0].signals.len(), 1);
+        assert_eq!(ldf.schedule_tables.len(), 1);
+
+        // All errors, in file order, followed by the missing sections
+        let locations: Vec<(usize, &str)> = errors
+            .iter()
+            .map(|error| (error.line, error.section.as_str()))
+            .collect();
+        assert_eq!(
+            locations,
+            vec![
+                (13, "Signals"),
+                (18, "Frames"),
+                (22, "Frames"),
+                (27, "Vendor_extension"),
+                (34, "Node_attributes"),
+            ]
+        );
+        assert_eq!(errors[0].expected.as_deref(), Some(","));
+        assert_eq!(errors[4].message, "missing Node_attributes section");
+        assert_eq!(
+            errors[4].hint().as_deref(),
+            Some("add a `Node_attributes { ... }` section")
+        );
+
+        // The strict parser stops at the first error
+        assert_eq!(LinLdf::parse(input).err(), Some(errors[0].clone()));
+    }
+
+    #[test]
+    fn test_parse_spans() {
+        let input = r#"LIN_description_file ;
+LIN_protocol_version = "2.1" ;
+LIN_language_version = "2.1" ;
+LIN_speed = 19.2 kbps ;
+
+Nodes {
+    Master: Master, 5 ms, 0.1 ms ;
+    Slaves: Slave1, Slave2 ;
+}
+
+Signals {
+    Signal1: 8, 0, Master, Slave1, Slave2 ; // comment
+    Signal2: 8, 0x1FF Master ;
+}
+
+Frames {
+    Frame1: 0x10, Master, 1 {
+        Signal1, 0 ;
+    }
+}
+
+Node_attributes {
+    Slave1 {
+        LIN_protocol = "2.1" ;
+        configured_NAD = 0x01 ;
+        product_id = 0x1E, 0x1 ;
+        response_error = Signal1 ;
+    }
+}
+
+Schedule_tables {
+    Table1 {
+        AssignFrameId { Slave1, Frame1 } delay 10 ms ;
+    }
+}
+"#;
+        let text = |span: LdfSpan| &input[span.start..span.end];
+        let (ldf, errors) = LinLdf::parse_with_recovery(input);
+        assert_eq!(errors.len(), 1);
+
+        // Entries span from their first character to their closing `;` or `}`
+        let signal = &ldf.signals[0];
+        assert_eq!(text(signal.span), "Signal1: 8, 0, Master, Slave1, Slave2 ;");
+        assert_eq!((signal.span.line, signal.span.column), (12, 5));
+        let references: Vec<(&str, usize, usize)> = signal
+            .references
+            .iter()
+            .map(|reference| (text(reference.span), reference.span.line, reference.span.column))
+            .collect();
+        assert_eq!(
+            references,
+            vec![("Master", 12, 20), ("Slave1", 12, 28), ("Slave2", 12, 36)]
+        );
+
+        let frame = &ldf.frames[0];
+        assert!(text(frame.span).starts_with("Frame1: 0x10, Master, 1 {"));
+        assert!(text(frame.span).ends_with('}'));
+        assert_eq!(text(frame.references[0].span), "Master");
+        assert_eq!(text(frame.signals[0].span), "Signal1, 0 ;");
+        assert_eq!(frame.signals[0].references[0].span.line, 18);
+
+        assert_eq!(ldf.nodes.master.span.line, 7);
+        assert_eq!(text(ldf.nodes.slaves[1].span), "Slave2");
+        assert_eq!(text(ldf.node_attributes[0].references[1].span), "Signal1");
+
+        let frame_delay = &ldf.schedule_tables[0].frame_delays[0];
+        assert_eq!(text(frame_delay.span), "AssignFrameId { Slave1, Frame1 } delay 10 ms ;");
+        let references: Vec<&str> = frame_delay
+            .references
+            .iter()
+            .map(|reference| text(reference.span))
+            .collect();
+        assert_eq!(references, vec!["Slave1", "Frame1"]);
+
+        // Sections in file order, the broken entry does not shift the spans
+        let sections: Vec<(&str, usize)> = ldf
+            .section_spans
+            .iter()
+            .map(|section| (section.name.as_str(), section.span.line))
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                ("header", 1),
+                ("Nodes", 6),
+                ("Signals", 11),
+                ("Frames", 16),
+                ("Node_attributes", 22),
+                ("Schedule_tables", 31),
+            ]
+        );
+        assert!(text(ldf.section_spans[2].span).starts_with("Signals {"));
+        assert!(text(ldf.section_spans[2].span).ends_with("0x1FF Master ;\n}"));
+    }
+
+    /// Property test: parsing never panics, whatever the input. Truncates and mutates a valid file
+    /// with a fixed seed, so failures are reproducible without a fuzzing toolchain.
+    #[test]
+    fn test_parse_never_panics() {
+        let input = r#"
+            LIN_description_file ;
+            LIN_protocol_version = "2.1" ;
+            LIN_language_version = "2.1" ;
+            LIN_speed = 19.2 kbps ;
+            Channel_name = "DB" ;
+
+            Nodes {
+                Master: Master, 5 ms, 0.1 ms ;
+                Slaves: Slave1, Slave2 ;
+            }
+
+            Node_composition {
+                configuration Cfg1 {
+                    Composite1 { Slave1, Slave2 } ;
+                }
+            }
+
+            Signals {
+                Signal1: 10, 0x1F, Master, Slave1 ;
+                Signal2: 16, {0x00, 0xFF}, Slave1, Master ;
+            }
+
+            Diagnostic_signals {
+                MasterReqB0: 8, 0 ;
+                SlaveRespB0: 8, 0 ;
+            }
+
+            Frames {
+                Frame1: 0x10, Master, 2 {
+                    Signal1, 0 ;
+                }
+                Frame2: 17, Slave1, 2 {
+                    Signal2, 0 ;
+                }
+            }
+
+            Sporadic_frames {
+                SF1: Frame1, Frame2 ;
+            }
+
+            Event_triggered_frames {
+                ETF1: Table1, 0x3A, Frame2 ;
+            }
+
+            Diagnostic_frames {
+                MasterReq: 0x3C {
+                    MasterReqB0, 0 ;
+                }
+                SlaveResp: 61 {
+                    SlaveRespB0, 0 ;
+                }
+            }
+
+            Node_attributes {
+                Slave1 {
+                    LIN_protocol = "2.1" ;
+                    configured_NAD = 0x01 ;
+                    initial_NAD = 2 ;
+                    product_id = 0x1234, 0x5678, 1 ;
+                    response_error = Signal2 ;
+                    P2_min = 100 ms ;
+                    response_tolerance = 38 % ;
+                    configurable_frames {
+                        Frame1 = 0x1001 ;
+                        Frame2 ;
+                    }
+                }
+            }
+
+            Schedule_tables {
+                Table1 {
+                    Frame1 delay 10 ms ;
+                    AssignNAD { Slave1 } delay 10.5 ms ;
+                    DataDump { Slave1, 0x01, 0x02, 0x03, 0x04, 0x05 } delay 10 ms ;
+                    AssignFrameIdRange { Slave1, 0, 0x10, 0x11, 0xFF, 0xFF } delay 10 ms ;
+                    FreeFormat { 0, 1, 2, 3, 4, 5, 6, 7 } delay 10 ms ;
+                }
+            }
+
+            Signal_groups {
+                Group1: 16 {
+                    Signal1, 0 ;
+                }
+            }
+
+            Signal_encoding_types {
+                ENC1 {
+                    logical_value, 0x0F, "Error" ;
+                    physical_value, 0, 0xFFFE, 1E-05, -.5, "V" ;
+                    bcd_value ;
+                    ascii_value ;
+                }
+            }
+
+            Signal_representation {
+                ENC1: Signal1, Signal2 ;
+            }
+
+            Vendor_extension {
+                Setting = "}" ;
+            }
+        "#;
+        assert!(LinLdf::parse(input).is_ok());
+
+        let parse_catching_panics = |input: &str| {
+            let result = std::panic::catch_unwind(|| {
+                let _ = LinLdf::parse(input);
+            });
+            assert!(result.is_ok(), "Parsing panicked for input:\n{}", input);
+        };
+
+        // Every truncation of the file
+        for (i, _) in input.char_indices() {
+            parse_catching_panics(&input[..i]);
+        }
+
+        // Random replacements of a few characters with tokens that are likely to trip up a parser
+        let tokens = [
+            "",
+            "0",
+            "0x",
+            "0x1FF",
+            "0xFFFFFFFFFFFFFFFFFF",
+            "99999999999",
+            "-",
+            ".",
+            "1e",
+            "1e999",
+            "{",
+            "}",
+            ";",
+            ",",
+            ":",
+            "=",
+            "\"",
+            "/*",
+            "//",
+            "\n",
+            "é",
+            "Frame1",
+            "ms",
+            "delay",
+            "Frames",
+            "Nodes {",
+        ];
+        let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+        let mut random = |n: usize| {
+            // xorshift64
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % n as u64) as usize
+        };
+        for _ in 0..2_000 {
+            let mut mutated = input.to_string();
+            for _ in 0..1 + random(3) {
+                let mut start = random(mutated.len());
+                while !mutated.is_char_boundary(start) {
+                    start -= 1;
+                }
+                let end = mutated[start..].chars().next().map_or(start, |c| start + c.len_utf8());
+                mutated.replace_range(start..end, tokens[random(tokens.len())]);
+            }
+            parse_catching_panics(&mutated);
+        }
+    }
 }
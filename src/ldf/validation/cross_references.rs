@@ -0,0 +1,441 @@
+use crate::ldf::ldf_schedule_tables::LdfScheduleCommand;
+use crate::ldf::validation::{reference_span, LdfFinding, LdfRule};
+use crate::ldf::LinLdf;
+use std::collections::HashSet;
+
+/// Names that can be referenced, by kind
+struct Names<'a> {
+    nodes: HashSet<&'a str>,
+    slaves: HashSet<&'a str>,
+    signals: HashSet<&'a str>,
+    diagnostic_signals: HashSet<&'a str>,
+    /// Unconditional frames
+    unconditional_frames: HashSet<&'a str>,
+    /// Unconditional, sporadic, event triggered and diagnostic frames
+    frames: HashSet<&'a str>,
+    schedule_tables: HashSet<&'a str>,
+    encoding_types: HashSet<&'a str>,
+}
+
+impl<'a> Names<'a> {
+    fn new(ldf: &'a LinLdf) -> Names<'a> {
+        let slaves: HashSet<&str> = ldf.nodes.slaves.iter().map(|node| node.name.as_str()).collect();
+        let unconditional_frames: HashSet<&str> = ldf.frames.iter().map(|frame| frame.frame_name.as_str()).collect();
+
+        let mut nodes = slaves.clone();
+        nodes.insert(ldf.nodes.master.name.as_str());
+
+        let mut frames = unconditional_frames.clone();
+        frames.extend(ldf.sporadic_frames.iter().map(|frame| frame.name.as_str()));
+        frames.extend(ldf.event_triggered_frames.iter().map(|frame| frame.frame_name.as_str()));
+        frames.extend(ldf.diagnostic_frames.iter().map(|frame| frame.frame_name.as_str()));
+
+        Names {
+            nodes,
+            slaves,
+            signals: ldf.signals.iter().map(|signal| signal.name.as_str()).collect(),
+            diagnostic_signals: ldf
+                .diagnostic_signals
+                .iter()
+                .map(|signal| signal.name.as_str())
+                .collect(),
+            unconditional_frames,
+            frames,
+            schedule_tables: ldf
+                .schedule_tables
+                .iter()
+                .map(|table| table.schedule_table_name.as_str())
+                .collect(),
+            encoding_types: ldf
+                .signal_encoding_types
+                .iter()
+                .map(|encoding_type| encoding_type.encoding_type_name.as_str())
+                .collect(),
+        }
+    }
+}
+
+/// `REF001` to `REF007`: every name reference in the file points to a defined element
+pub(crate) fn check(ldf: &LinLdf, findings: &mut Vec<LdfFinding>) {
+    let names = Names::new(ldf);
+
+    for signal in &ldf.signals {
+        let publisher = signal.published_by.as_str();
+        if !names.nodes.contains(publisher) {
+            let span = reference_span(&signal.references, publisher, signal.span);
+            let message = format!("signal `{}` is published by unknown node `{}`", signal.name, publisher);
+            findings.push(LdfFinding::new(LdfRule::UnknownNode, "Signals", span, message));
+        }
+        for subscriber in &signal.subscribed_by {
+            if !names.nodes.contains(subscriber.as_str()) {
+                let span = reference_span(&signal.references, subscriber, signal.span);
+                let message = format!(
+                    "signal `{}` is subscribed to by unknown node `{}`",
+                    signal.name, subscriber
+                );
+                findings.push(LdfFinding::new(LdfRule::UnknownNode, "Signals", span, message));
+            }
+        }
+    }
+
+    for frame in &ldf.frames {
+        let publisher = frame.published_by.as_str();
+        if !names.nodes.contains(publisher) {
+            let span = reference_span(&frame.references, publisher, frame.span);
+            let message = format!(
+                "frame `{}` is published by unknown node `{}`",
+                frame.frame_name, publisher
+            );
+            findings.push(LdfFinding::new(LdfRule::UnknownNode, "Frames", span, message));
+        }
+        for signal in &frame.signals {
+            if !names.signals.contains(signal.signal_name.as_str()) {
+                let span = reference_span(&signal.references, &signal.signal_name, signal.span);
+                let message = format!(
+                    "frame `{}` contains unknown signal `{}`",
+                    frame.frame_name, signal.signal_name
+                );
+                findings.push(LdfFinding::new(LdfRule::UnknownSignal, "Frames", span, message));
+            }
+        }
+    }
+
+    for frame in &ldf.sporadic_frames {
+        for associated_frame in &frame.associated_frames {
+            if !names.unconditional_frames.contains(associated_frame.as_str()) {
+                let span = reference_span(&frame.references, associated_frame, frame.span);
+                let message = format!(
+                    "sporadic frame `{}` uses unknown unconditional frame `{}`",
+                    frame.name, associated_frame
+                );
+                findings.push(LdfFinding::new(LdfRule::UnknownFrame, "Sporadic_frames", span, message));
+            }
+        }
+    }
+
+    for frame in &ldf.event_triggered_frames {
+        if let Some(schedule_table) = &frame.collision_resolving_schedule_table {
+            if !names.schedule_tables.contains(schedule_table.as_str()) {
+                let span = reference_span(&frame.references, schedule_table, frame.span);
+                let message = format!(
+                    "event triggered frame `{}` resolves collisions with unknown schedule table `{}`",
+                    frame.frame_name, schedule_table
+                );
+                findings.push(LdfFinding::new(
+                    LdfRule::UnknownScheduleTable,
+                    "Event_triggered_frames",
+                    span,
+                    message,
+                ));
+            }
+        }
+        for associated_frame in &frame.associated_frames {
+            if !names.unconditional_frames.contains(associated_frame.as_str()) {
+                let span = reference_span(&frame.references, associated_frame, frame.span);
+                let message = format!(
+                    "event triggered frame `{}` uses unknown unconditional frame `{}`",
+                    frame.frame_name, associated_frame
+                );
+                findings.push(LdfFinding::new(
+                    LdfRule::UnknownFrame,
+                    "Event_triggered_frames",
+                    span,
+                    message,
+                ));
+            }
+        }
+    }
+
+    for frame in &ldf.diagnostic_frames {
+        for signal in &frame.signals {
+            if !names.diagnostic_signals.contains(signal.signal_name.as_str()) {
+                let span = reference_span(&signal.references, &signal.signal_name, signal.span);
+                let message = format!(
+                    "diagnostic frame `{}` contains unknown diagnostic signal `{}`",
+                    frame.frame_name, signal.signal_name
+                );
+                findings.push(LdfFinding::new(
+                    LdfRule::UnknownDiagnosticSignal,
+                    "Diagnostic_frames",
+                    span,
+                    message,
+                ));
+            }
+        }
+    }
+
+    for node in &ldf.node_attributes {
+        let references = &node.references;
+        if !names.slaves.contains(node.node_name.as_str()) {
+            let span = reference_span(references, &node.node_name, node.span);
+            let message = format!(
+                "node attributes are given for `{}`, which is not a slave node",
+                node.node_name
+            );
+            findings.push(LdfFinding::new(LdfRule::UnknownSlave, "Node_attributes", span, message));
+        }
+        let signals = node.response_error.iter().chain(&node.fault_state_signals);
+        for signal in signals {
+            if !names.signals.contains(signal.as_str()) {
+                let span = reference_span(references, signal, node.span);
+                let message = format!("node `{}` uses unknown signal `{}`", node.node_name, signal);
+                findings.push(LdfFinding::new(
+                    LdfRule::UnknownSignal,
+                    "Node_attributes",
+                    span,
+                    message,
+                ));
+            }
+        }
+        for frame in &node.configurable_frames {
+            if !names.frames.contains(frame.frame_name.as_str()) {
+                let span = reference_span(&frame.references, &frame.frame_name, frame.span);
+                let message = format!(
+                    "node `{}` has unknown configurable frame `{}`",
+                    node.node_name, frame.frame_name
+                );
+                findings.push(LdfFinding::new(LdfRule::UnknownFrame, "Node_attributes", span, message));
+            }
+        }
+    }
+
+    for configuration in &ldf.node_compositions {
+        for composite_node in &configuration.composite_nodes {
+            for logical_node in &composite_node.logical_nodes {
+                if !names.slaves.contains(logical_node.as_str()) {
+                    let span = reference_span(&composite_node.references, logical_node, composite_node.span);
+                    let message = format!(
+                        "composite node `{}` is made of `{}`, which is not a slave node",
+                        composite_node.name, logical_node
+                    );
+                    findings.push(LdfFinding::new(
+                        LdfRule::UnknownSlave,
+                        "Node_composition",
+                        span,
+                        message,
+                    ));
+                }
+            }
+        }
+    }
+
+    for table in &ldf.schedule_tables {
+        for frame_delay in &table.frame_delays {
+            let (node_name, frame_name) = match &frame_delay.command {
+                LdfScheduleCommand::Frame { frame_name } => (None, Some(frame_name)),
+                LdfScheduleCommand::AssignNad { node_name }
+                | LdfScheduleCommand::DataDump { node_name, .. }
+                | LdfScheduleCommand::SaveConfiguration { node_name }
+                | LdfScheduleCommand::AssignFrameIdRange { node_name, .. } => (Some(node_name), None),
+                LdfScheduleCommand::AssignFrameId { node_name, frame_name }
+                | LdfScheduleCommand::UnassignFrameId { node_name, frame_name } => (Some(node_name), Some(frame_name)),
+                LdfScheduleCommand::MasterReq
+                | LdfScheduleCommand::SlaveResp
+                | LdfScheduleCommand::ConditionalChangeNad { .. }
+                | LdfScheduleCommand::FreeFormat { .. } => (None, None),
+            };
+
+            if let Some(node_name) = node_name.filter(|node_name| !names.slaves.contains(node_name.as_str())) {
+                let span = reference_span(&frame_delay.references, node_name, frame_delay.span);
+                let message = format!(
+                    "schedule table `{}` configures `{}`, which is not a slave node",
+                    table.schedule_table_name, node_name
+                );
+                findings.push(LdfFinding::new(LdfRule::UnknownSlave, "Schedule_tables", span, message));
+            }
+            if let Some(frame_name) = frame_name.filter(|frame_name| !names.frames.contains(frame_name.as_str())) {
+                let span = reference_span(&frame_delay.references, frame_name, frame_delay.span);
+                let message = format!(
+                    "schedule table `{}` uses unknown frame `{}`",
+                    table.schedule_table_name, frame_name
+                );
+                findings.push(LdfFinding::new(LdfRule::UnknownFrame, "Schedule_tables", span, message));
+            }
+        }
+    }
+
+    for group in &ldf.signal_groups {
+        for (signal, _) in &group.signals {
+            if !names.signals.contains(signal.as_str()) {
+                let span = reference_span(&group.references, signal, group.span);
+                let message = format!("signal group `{}` contains unknown signal `{}`", group.name, signal);
+                findings.push(LdfFinding::new(LdfRule::UnknownSignal, "Signal_groups", span, message));
+            }
+        }
+    }
+
+    for representation in &ldf.signal_representations {
+        let references = &representation.references;
+        let encoding_type = representation.encoding_type_name.as_str();
+        if !names.encoding_types.contains(encoding_type) {
+            let span = reference_span(references, encoding_type, representation.span);
+            let message = format!("signal representation uses unknown encoding type `{}`", encoding_type);
+            findings.push(LdfFinding::new(
+                LdfRule::UnknownEncodingType,
+                "Signal_representation",
+                span,
+                message,
+            ));
+        }
+        for signal in &representation.signal_names {
+            if !names.signals.contains(signal.as_str()) {
+                let span = reference_span(references, signal, representation.span);
+                let message = format!(
+                    "encoding type `{}` is applied to unknown signal `{}`",
+                    encoding_type, signal
+                );
+                findings.push(LdfFinding::new(
+                    LdfRule::UnknownSignal,
+                    "Signal_representation",
+                    span,
+                    message,
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ldf::validation::{findings_of, LdfRule};
+    use crate::ldf::LinLdf;
+
+    #[test]
+    fn test_validate_cross_references() {
+        let input = r#"
+            LIN_description_file ;
+            LIN_protocol_version = "2.1" ;
+            LIN_language_version = "2.1" ;
+            LIN_speed = 19.2 kbps ;
+
+            Nodes {
+                Master: Master, 5 ms, 0.1 ms ;
+                Slaves: Slave1, Slave2 ;
+            }
+
+            Signals {
+                Signal1: 8, 0, Master, Slave1 ;
+                Signal2: 8, 0, Slave3, Master, Slave4 ;
+            }
+
+            Diagnostic_signals {
+                MasterReqB0: 8, 0 ;
+            }
+
+            Frames {
+                Frame1: 0x10, Master, 2 {
+                    Signal1, 0 ;
+                    Signal9, 8 ;
+                }
+            }
+
+            Event_triggered_frames {
+                ETF1: Collisions, 0x3A, Frame1, Frame7 ;
+            }
+
+            Diagnostic_frames {
+                MasterReq: 0x3C {
+                    MasterReqB0, 0 ;
+                    MasterReqB1, 8 ;
+                }
+            }
+
+            Node_attributes {
+                Slave1 {
+                    LIN_protocol = "2.1" ;
+                    configured_NAD = 0x01 ;
+                    product_id = 0x1E, 0x1 ;
+                    response_error = Signal1 ;
+                    configurable_frames {
+                        Frame1 ;
+                        ETF1 ;
+                        MasterReq ;
+                    }
+                }
+                Master {
+                    LIN_protocol = "2.1" ;
+                    configured_NAD = 0x02 ;
+                    product_id = 0x1E, 0x1 ;
+                    response_error = Signal5 ;
+                }
+            }
+
+            Schedule_tables {
+                Table1 {
+                    Frame1 delay 10 ms ;
+                    Frame2 delay 10 ms ;
+                    AssignNAD { Slave5 } delay 10 ms ;
+                    MasterReq delay 10 ms ;
+                }
+            }
+
+            Signal_encoding_types {
+                Enc1 {
+                    logical_value, 0, "off" ;
+                }
+            }
+
+            Signal_representation {
+                Enc1: Signal1, Signal2 ;
+                Enc2: Signal3 ;
+            }
+        "#;
+
+        let ldf = LinLdf::parse(input).unwrap();
+        let findings = findings_of(&ldf, "REF");
+        let found: Vec<(&str, &str)> = findings
+            .iter()
+            .map(|finding| (finding.rule.id(), finding.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("REF001", "signal `Signal2` is published by unknown node `Slave3`"),
+                ("REF001", "signal `Signal2` is subscribed to by unknown node `Slave4`"),
+                ("REF003", "frame `Frame1` contains unknown signal `Signal9`"),
+                (
+                    "REF006",
+                    "event triggered frame `ETF1` resolves collisions with unknown schedule table `Collisions`"
+                ),
+                (
+                    "REF005",
+                    "event triggered frame `ETF1` uses unknown unconditional frame `Frame7`"
+                ),
+                (
+                    "REF004",
+                    "diagnostic frame `MasterReq` contains unknown diagnostic signal `MasterReqB1`"
+                ),
+                (
+                    "REF002",
+                    "node attributes are given for `Master`, which is not a slave node"
+                ),
+                ("REF003", "node `Master` uses unknown signal `Signal5`"),
+                ("REF005", "schedule table `Table1` uses unknown frame `Frame2`"),
+                (
+                    "REF002",
+                    "schedule table `Table1` configures `Slave5`, which is not a slave node"
+                ),
+                ("REF007", "signal representation uses unknown encoding type `Enc2`"),
+                ("REF003", "encoding type `Enc2` is applied to unknown signal `Signal3`"),
+            ]
+        );
+
+        // Findings point at the reference
+        let unknown_signal = &findings[2];
+        assert_eq!(&input[unknown_signal.span.start..unknown_signal.span.end], "Signal9");
+        assert_eq!(unknown_signal.rule, LdfRule::UnknownSignal);
+
+        let expected = [
+            "error[REF003]: frame `Frame1` contains unknown signal `Signal9`",
+            "  --> line 24, column 21 (Frames section)",
+            "   |",
+            "24 |                     Signal9, 8 ;",
+            "   |                     ^^^^^^^",
+        ];
+        assert_eq!(unknown_signal.render(input), expected.join("\n"));
+        assert_eq!(
+            unknown_signal.to_string(),
+            "error[REF003] line 24, column 21 (Frames section): frame `Frame1` contains unknown signal `Signal9`"
+        );
+    }
+}
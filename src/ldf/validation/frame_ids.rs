@@ -0,0 +1,182 @@
+use crate::ldf::ldf_span::LdfSpan;
+use crate::ldf::validation::{LdfFinding, LdfRule};
+use crate::ldf::LinLdf;
+use std::collections::HashMap;
+
+/// Frame IDs 0x3C (MasterReq) and 0x3D (SlaveResp) are for diagnostic frames, 0x3E and 0x3F are reserved
+const RESERVED_FRAME_IDS: std::ops::RangeInclusive<u8> = 0x3C..=0x3F;
+
+/// Highest frame ID, the protected identifier has 6 bits for it
+const MAX_FRAME_ID: u8 = 0x3F;
+
+/// `ID001` to `ID005`: frame IDs are in range, unique and only diagnostic frames use 0x3C and 0x3D, frame sizes
+/// are 1 to 8 bytes.
+///
+/// Sporadic frames are not checked, they have no frame ID of their own.
+pub(crate) fn check(ldf: &LinLdf, findings: &mut Vec<LdfFinding>) {
+    // Frames with an ID, in file order of their sections: (section, kind, name, ID, span)
+    let mut frames: Vec<(&str, &str, &str, u8, LdfSpan)> = Vec::new();
+    frames.extend(ldf.frames.iter().map(|frame| {
+        let name = frame.frame_name.as_str();
+        ("Frames", "frame", name, frame.frame_id, frame.span)
+    }));
+    frames.extend(ldf.event_triggered_frames.iter().map(|frame| {
+        let name = frame.frame_name.as_str();
+        (
+            "Event_triggered_frames",
+            "event triggered frame",
+            name,
+            frame.frame_id,
+            frame.span,
+        )
+    }));
+    frames.extend(ldf.diagnostic_frames.iter().map(|frame| {
+        let name = frame.frame_name.as_str();
+        (
+            "Diagnostic_frames",
+            "diagnostic frame",
+            name,
+            frame.frame_id,
+            frame.span,
+        )
+    }));
+
+    let mut first_with_id: HashMap<u8, (&str, &str)> = HashMap::new();
+    for (section, kind, name, frame_id, span) in frames {
+        let diagnostic = section == "Diagnostic_frames";
+
+        if frame_id > MAX_FRAME_ID {
+            let message = format!(
+                "{} `{}` has ID {:#04X}, but frame IDs are 0x00 to {:#04X}",
+                kind, name, frame_id, MAX_FRAME_ID
+            );
+            findings.push(LdfFinding::new(LdfRule::FrameIdOutOfRange, section, span, message));
+        } else if diagnostic && !matches!(frame_id, 0x3C | 0x3D) {
+            let message = format!(
+                "diagnostic frame `{}` has ID {:#04X}, but diagnostic frames use 0x3C (MasterReq) or 0x3D (SlaveResp)",
+                name, frame_id
+            );
+            findings.push(LdfFinding::new(LdfRule::DiagnosticFrameId, section, span, message));
+        } else if !diagnostic && RESERVED_FRAME_IDS.contains(&frame_id) {
+            let message = format!(
+                "{} `{}` has ID {:#04X}, which is reserved (0x3C and 0x3D for diagnostic frames, 0x3E and 0x3F for extensions)",
+                kind, name, frame_id
+            );
+            findings.push(LdfFinding::new(LdfRule::ReservedFrameId, section, span, message));
+        }
+
+        if let Some((other_kind, other_name)) = first_with_id.get(&frame_id) {
+            let message = format!(
+                "{} `{}` has ID {:#04X}, which is already used by {} `{}`",
+                kind, name, frame_id, other_kind, other_name
+            );
+            findings.push(LdfFinding::new(LdfRule::DuplicateFrameId, section, span, message));
+        } else {
+            first_with_id.insert(frame_id, (kind, name));
+        }
+    }
+
+    for frame in &ldf.frames {
+        if !(1..=8).contains(&frame.frame_size) {
+            let message = format!(
+                "frame `{}` has {} bytes, but frames are 1 to 8 bytes long",
+                frame.frame_name, frame.frame_size
+            );
+            findings.push(LdfFinding::new(LdfRule::FrameSize, "Frames", frame.span, message));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ldf::validation::findings_of;
+    use crate::ldf::LinLdf;
+
+    #[test]
+    fn test_validate_frame_ids() {
+        let input = r#"
+            LIN_description_file ;
+            LIN_protocol_version = "2.1" ;
+            LIN_language_version = "2.1" ;
+            LIN_speed = 19.2 kbps ;
+
+            Nodes {
+                Master: Master, 5 ms, 0.1 ms ;
+                Slaves: Slave1 ;
+            }
+
+            Signals {
+                Signal1: 8, 0, Master, Slave1 ;
+            }
+
+            Diagnostic_signals {
+                MasterReqB0: 8, 0 ;
+            }
+
+            Frames {
+                Frame1: 0x10, Master, 1 {
+                    Signal1, 0 ;
+                }
+                Frame2: 0x10, Master, 9 {
+                }
+                Frame3: 0x3D, Master, 1 {
+                }
+                Frame4: 0x40, Master, 0 {
+                }
+            }
+
+            Event_triggered_frames {
+                ETF1: 0x3A, Frame1 ;
+                ETF2: 0x3A, Frame1 ;
+            }
+
+            Diagnostic_frames {
+                MasterReq: 0x3C {
+                    MasterReqB0, 0 ;
+                }
+                SlaveResp: 0x3E {
+                }
+            }
+
+            Node_attributes {
+                Slave1 {
+                    LIN_protocol = "2.1" ;
+                    configured_NAD = 0x01 ;
+                    product_id = 0x1E, 0x1 ;
+                }
+            }
+
+            Schedule_tables {
+                Table1 {
+                    Frame1 delay 10 ms ;
+                }
+            }
+        "#;
+
+        let ldf = LinLdf::parse(input).unwrap();
+        let findings = findings_of(&ldf, "ID");
+        let found: Vec<(&str, &str)> = findings
+            .iter()
+            .map(|finding| (finding.rule.id(), finding.message.as_str()))
+            .collect();
+        let expected = vec![
+            ("ID002", "frame `Frame2` has ID 0x10, which is already used by frame `Frame1`"),
+            ("ID005", "frame `Frame2` has 9 bytes, but frames are 1 to 8 bytes long"),
+            (
+                "ID003",
+                "frame `Frame3` has ID 0x3D, which is reserved (0x3C and 0x3D for diagnostic frames, 0x3E and 0x3F for extensions)",
+            ),
+            ("ID001", "frame `Frame4` has ID 0x40, but frame IDs are 0x00 to 0x3F"),
+            ("ID005", "frame `Frame4` has 0 bytes, but frames are 1 to 8 bytes long"),
+            (
+                "ID002",
+                "event triggered frame `ETF2` has ID 0x3A, which is already used by event triggered frame `ETF1`",
+            ),
+            (
+                "ID004",
+                "diagnostic frame `SlaveResp` has ID 0x3E, but diagnostic frames use 0x3C (MasterReq) or 0x3D (SlaveResp)",
+            ),
+        ];
+        assert_eq!(found, expected);
+    }
+}
@@ -0,0 +1,278 @@
+use crate::ldf::ldf_frames::{LdfFrame, LdfFrameSignal};
+use crate::ldf::ldf_signals::{LdfSignal, LdfSignalInitValue};
+use crate::ldf::validation::{reference_span, LdfFinding, LdfRule};
+use crate::ldf::LinLdf;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Signal of a frame with the bits it occupies, `start` to `end` (exclusive)
+struct Placement<'a> {
+    frame_signal: &'a LdfFrameSignal,
+    signal: &'a LdfSignal,
+    start: usize,
+    end: usize,
+}
+
+/// `LAY001` to `LAY004`: the signals of each unconditional frame fit the frame, don't overlap, are byte-aligned
+/// if they are byte arrays and are published by the publisher of the frame
+pub(crate) fn check(ldf: &LinLdf, findings: &mut Vec<LdfFinding>) {
+    let signals: HashMap<&str, &LdfSignal> = ldf
+        .signals
+        .iter()
+        .map(|signal| (signal.name.as_str(), signal))
+        .collect();
+
+    for frame in &ldf.frames {
+        // Unknown signals are reported by REF003
+        let layout: Vec<Placement> = frame
+            .signals
+            .iter()
+            .filter_map(|frame_signal| {
+                let signal = signals.get(frame_signal.signal_name.as_str())?;
+                let start = usize::from(frame_signal.start_bit);
+                Some(Placement {
+                    frame_signal,
+                    signal,
+                    start,
+                    end: start + usize::from(signal.signal_size),
+                })
+            })
+            .collect();
+
+        let frame_bits = usize::from(frame.frame_size) * 8;
+        let mut frame_findings = Vec::new();
+        for (i, placement) in layout.iter().enumerate() {
+            let Placement {
+                frame_signal,
+                signal,
+                start,
+                end,
+            } = placement;
+            let name = &frame_signal.signal_name;
+            let span = reference_span(&frame_signal.references, name, frame_signal.span);
+
+            if *end > frame_bits {
+                let message = format!(
+                    "signal `{}` in frame `{}` occupies bits {}, but the frame only has {} bits ({} bytes)",
+                    name,
+                    frame.frame_name,
+                    bit_range(*start, *end),
+                    frame_bits,
+                    frame.frame_size
+                );
+                frame_findings.push(LdfFinding::new(LdfRule::SignalOutsideFrame, "Frames", span, message));
+            }
+
+            for other in &layout[..i] {
+                if *start < other.end && other.start < *end {
+                    let message = format!(
+                        "signal `{}` in frame `{}` overlaps signal `{}` in bits {}",
+                        name,
+                        frame.frame_name,
+                        other.frame_signal.signal_name,
+                        bit_range((*start).max(other.start), (*end).min(other.end))
+                    );
+                    frame_findings.push(LdfFinding::new(LdfRule::OverlappingSignals, "Frames", span, message));
+                }
+            }
+
+            if matches!(signal.init_value, LdfSignalInitValue::Array(_)) && start % 8 != 0 {
+                let message = format!(
+                    "byte array signal `{}` in frame `{}` starts at bit {}, which is not a multiple of 8",
+                    name, frame.frame_name, start
+                );
+                frame_findings.push(LdfFinding::new(LdfRule::UnalignedByteArray, "Frames", span, message));
+            }
+
+            if signal.published_by != frame.published_by {
+                let message = format!(
+                    "signal `{}` in frame `{}` is published by `{}`, but the frame is published by `{}`",
+                    name, frame.frame_name, signal.published_by, frame.published_by
+                );
+                frame_findings.push(LdfFinding::new(
+                    LdfRule::SignalPublisherMismatch,
+                    "Frames",
+                    span,
+                    message,
+                ));
+            }
+        }
+
+        if !frame_findings.is_empty() {
+            let map = bit_map(frame, &layout);
+            findings.extend(frame_findings.into_iter().map(|finding| finding.with_note(map.clone())));
+        }
+    }
+}
+
+/// Bits `start` to `end` (exclusive), e.g. `5-12` or `3`
+fn bit_range(start: usize, end: usize) -> String {
+    if end - start <= 1 {
+        start.to_string()
+    } else {
+        format!("{}-{}", start, end - 1)
+    }
+}
+
+/// Bit map of the frame, a row per byte with the most significant bit on the left. Each signal is shown
+/// with a letter, overlapping bits with `!`:
+/// ```text
+/// frame `Frame1` (2 bytes, published by Master)
+///          7 6 5 4 3 2 1 0
+/// byte 0 | B B B A A A A A
+/// byte 1 | . . . ! ! B B B
+/// A = Signal1, bits 0-4
+/// B = Signal2, bits 5-12
+/// ! = overlapping bits
+/// ```
+fn bit_map(frame: &LdfFrame, layout: &[Placement]) -> String {
+    let frame_bits = usize::from(frame.frame_size) * 8;
+    let bits = layout
+        .iter()
+        .map(|placement| placement.end)
+        .fold(frame_bits, usize::max);
+    let bytes = bits.div_ceil(8);
+
+    let mut cells = vec!['.'; bytes * 8];
+    for (i, placement) in layout.iter().enumerate() {
+        for cell in &mut cells[placement.start..placement.end] {
+            *cell = if *cell == '.' { letter(i) } else { '!' };
+        }
+    }
+
+    let mut map = String::new();
+    let _ = write!(
+        map,
+        "frame `{}` ({} bytes, published by {})\n         7 6 5 4 3 2 1 0",
+        frame.frame_name, frame.frame_size, frame.published_by
+    );
+    for byte in 0..bytes {
+        let row: Vec<String> = (0..8).rev().map(|bit| cells[byte * 8 + bit].to_string()).collect();
+        let _ = write!(map, "\nbyte {} | {}", byte, row.join(" "));
+        if byte * 8 >= frame_bits {
+            map.push_str("  (outside the frame)");
+        }
+    }
+    for (i, placement) in layout.iter().enumerate() {
+        let bits = bit_range(placement.start, placement.end);
+        let _ = write!(
+            map,
+            "\n{} = {}, bits {}",
+            letter(i),
+            placement.frame_signal.signal_name,
+            bits
+        );
+    }
+    if cells.contains(&'!') {
+        map.push_str("\n! = overlapping bits");
+    }
+    map
+}
+
+/// Letter of the `i`th signal in a bit map
+fn letter(i: usize) -> char {
+    match i {
+        0..=25 => (b'A' + i as u8) as char,
+        26..=51 => (b'a' + (i - 26) as u8) as char,
+        _ => '#',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ldf::validation::findings_of;
+    use crate::ldf::validation::LdfRule;
+    use crate::ldf::LinLdf;
+
+    #[test]
+    fn test_validate_frame_layout() {
+        let input = r#"
+            LIN_description_file ;
+            LIN_protocol_version = "2.1" ;
+            LIN_language_version = "2.1" ;
+            LIN_speed = 19.2 kbps ;
+
+            Nodes {
+                Master: Master, 5 ms, 0.1 ms ;
+                Slaves: Slave1 ;
+            }
+
+            Signals {
+                Signal1: 5, 0, Master, Slave1 ;
+                Signal2: 8, 0, Master, Slave1 ;
+                Signal3: 16, {0, 0}, Master, Slave1 ;
+                Signal4: 4, 0, Slave1, Master ;
+            }
+
+            Frames {
+                Frame1: 0x10, Master, 2 {
+                    Signal1, 0 ;
+                    Signal2, 3 ;
+                    Signal3, 12 ;
+                }
+                Frame2: 0x11, Master, 1 {
+                    Signal4, 0 ;
+                }
+            }
+
+            Node_attributes {
+                Slave1 {
+                    LIN_protocol = "2.1" ;
+                    configured_NAD = 0x01 ;
+                    product_id = 0x1E, 0x1 ;
+                }
+            }
+
+            Schedule_tables {
+                Table1 {
+                    Frame1 delay 10 ms ;
+                    Frame2 delay 10 ms ;
+                }
+            }
+        "#;
+
+        let ldf = LinLdf::parse(input).unwrap();
+        let findings = findings_of(&ldf, "LAY");
+        let found: Vec<(&str, &str)> = findings
+            .iter()
+            .map(|finding| (finding.rule.id(), finding.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("LAY002", "signal `Signal2` in frame `Frame1` overlaps signal `Signal1` in bits 3-4"),
+                (
+                    "LAY001",
+                    "signal `Signal3` in frame `Frame1` occupies bits 12-27, but the frame only has 16 bits (2 bytes)"
+                ),
+                (
+                    "LAY003",
+                    "byte array signal `Signal3` in frame `Frame1` starts at bit 12, which is not a multiple of 8"
+                ),
+                (
+                    "LAY004",
+                    "signal `Signal4` in frame `Frame2` is published by `Slave1`, but the frame is published by `Master`"
+                ),
+            ]
+        );
+        assert_eq!(findings[0].rule, LdfRule::OverlappingSignals);
+
+        let expected = [
+            "frame `Frame1` (2 bytes, published by Master)",
+            "         7 6 5 4 3 2 1 0",
+            "byte 0 | B B B ! ! A A A",
+            "byte 1 | C C C C . B B B",
+            "byte 2 | C C C C C C C C  (outside the frame)",
+            "byte 3 | . . . . C C C C  (outside the frame)",
+            "A = Signal1, bits 0-4",
+            "B = Signal2, bits 3-10",
+            "C = Signal3, bits 12-27",
+            "! = overlapping bits",
+        ];
+        assert_eq!(findings[0].note.as_deref(), Some(expected.join("\n").as_str()));
+
+        let rendered = findings[0].render(input);
+        assert!(rendered.contains("\n   = note: frame `Frame1` (2 bytes, published by Master)\n"));
+        assert!(rendered.contains("\n           byte 0 | B B B ! ! A A A\n"));
+    }
+}
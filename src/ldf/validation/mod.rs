@@ -0,0 +1,370 @@
+mod cross_references;
+mod frame_ids;
+mod frame_layout;
+mod node_attributes;
+mod protocol_versions;
+mod rule_sets;
+mod schedule_timing;
+mod signal_encodings;
+
+pub use rule_sets::LdfRuleSet;
+
+use crate::ldf::ldf_diagnostic::{snippet_at, LdfDiagnostic, LdfSeverity};
+use crate::ldf::ldf_span::{LdfNamedSpan, LdfSpan};
+use crate::ldf::LinLdf;
+use std::fmt;
+
+/// Rule checked by [`LinLdf::validate`].
+///
+/// Each rule has a stable identifier (e.g. `REF003`), so that findings can be filtered or suppressed
+/// by tools without depending on the message text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub enum LdfRule {
+    /// `REF001`: a signal or frame is published by or subscribed to by a node that is not in `Nodes`
+    UnknownNode,
+    /// `REF002`: a node that has to be a slave (e.g. in `Node_attributes`) is not in the `Slaves` of `Nodes`
+    UnknownSlave,
+    /// `REF003`: a signal that is not in `Signals` is used (e.g. in a frame or a signal representation)
+    UnknownSignal,
+    /// `REF004`: a diagnostic frame uses a signal that is not in `Diagnostic_signals`
+    UnknownDiagnosticSignal,
+    /// `REF005`: a frame that is not defined is used (e.g. in a schedule table or as configurable frame)
+    UnknownFrame,
+    /// `REF006`: an event triggered frame resolves collisions with a schedule table that is not defined
+    UnknownScheduleTable,
+    /// `REF007`: a signal representation uses an encoding type that is not in `Signal_encoding_types`
+    UnknownEncodingType,
+    /// `LAY001`: a signal doesn't fit in its frame (`start_bit + signal_size > frame_size * 8`)
+    SignalOutsideFrame,
+    /// `LAY002`: two signals of a frame occupy the same bits
+    OverlappingSignals,
+    /// `LAY003`: a byte array signal doesn't start at a multiple of 8 bits
+    UnalignedByteArray,
+    /// `LAY004`: a signal is published by another node than the frame that carries it
+    SignalPublisherMismatch,
+    /// `ID001`: a frame ID is above 0x3F
+    FrameIdOutOfRange,
+    /// `ID002`: two frames (unconditional, event triggered or diagnostic) have the same ID
+    DuplicateFrameId,
+    /// `ID003`: an unconditional or event triggered frame uses a reserved ID (0x3C to 0x3F)
+    ReservedFrameId,
+    /// `ID004`: a diagnostic frame has another ID than 0x3C (MasterReq) or 0x3D (SlaveResp)
+    DiagnosticFrameId,
+    /// `ID005`: a frame is shorter than 1 or longer than 8 bytes
+    FrameSize,
+    /// `SCH001`: a schedule table slot is not a multiple of the time base of the master, so it is stretched to the
+    /// next time base tick
+    DelayNotTimeBaseMultiple,
+    /// `SCH002`: a schedule table slot is shorter than the maximum transmission time of its frame (TFrame_Maximum)
+    SlotTooShort,
+    /// `ENC001`: a scalar signal is shorter than 1 or longer than 16 bits
+    ScalarSignalSize,
+    /// `ENC002`: a byte array signal is not 8, 16, 24, 32, 40, 48, 56 or 64 bits long
+    ArraySignalSize,
+    /// `ENC003`: a logical value or physical range of an encoding type doesn't fit the raw values of a signal it
+    /// represents
+    EncodingValueOutOfRange,
+    /// `ENC004`: two physical ranges of an encoding type overlap
+    OverlappingPhysicalRanges,
+    /// `NODE001`: the configured or initial NAD of a node is 0x00 (go to sleep), 0x7E (functional) or 0x7F (broadcast)
+    InvalidNad,
+    /// `NODE002`: two slave nodes have the same configured NAD
+    DuplicateNad,
+    /// `NODE003`: the supplier ID of a node has the reserved most significant bit set
+    ReservedSupplierId,
+    /// `NODE004`: the response error signal of a node is not a 1 bit scalar signal published by the node
+    ResponseErrorSignal,
+    /// `NODE005`: a node has no `response_error` signal, which is required from LIN 2.0 on
+    MissingResponseError,
+    /// `HDR001`: the header has a `Channel_name`, which was added in LIN 2.2
+    ChannelNameNotSupported,
+    /// `HDR002`: a node uses a newer `LIN_protocol` than the LIN version the file is validated against
+    NodeProtocolVersion,
+    /// `HDR003`: the `LIN_protocol_version` of the header is not a known version, so LIN 2.2 rules are applied
+    UnknownProtocolVersion,
+}
+
+impl LdfRule {
+    /// Stable identifier of the rule, e.g. `REF003`
+    pub fn id(self) -> &'static str {
+        match self {
+            LdfRule::UnknownNode => "REF001",
+            LdfRule::UnknownSlave => "REF002",
+            LdfRule::UnknownSignal => "REF003",
+            LdfRule::UnknownDiagnosticSignal => "REF004",
+            LdfRule::UnknownFrame => "REF005",
+            LdfRule::UnknownScheduleTable => "REF006",
+            LdfRule::UnknownEncodingType => "REF007",
+            LdfRule::SignalOutsideFrame => "LAY001",
+            LdfRule::OverlappingSignals => "LAY002",
+            LdfRule::UnalignedByteArray => "LAY003",
+            LdfRule::SignalPublisherMismatch => "LAY004",
+            LdfRule::FrameIdOutOfRange => "ID001",
+            LdfRule::DuplicateFrameId => "ID002",
+            LdfRule::ReservedFrameId => "ID003",
+            LdfRule::DiagnosticFrameId => "ID004",
+            LdfRule::FrameSize => "ID005",
+            LdfRule::DelayNotTimeBaseMultiple => "SCH001",
+            LdfRule::SlotTooShort => "SCH002",
+            LdfRule::ScalarSignalSize => "ENC001",
+            LdfRule::ArraySignalSize => "ENC002",
+            LdfRule::EncodingValueOutOfRange => "ENC003",
+            LdfRule::OverlappingPhysicalRanges => "ENC004",
+            LdfRule::InvalidNad => "NODE001",
+            LdfRule::DuplicateNad => "NODE002",
+            LdfRule::ReservedSupplierId => "NODE003",
+            LdfRule::ResponseErrorSignal => "NODE004",
+            LdfRule::MissingResponseError => "NODE005",
+            LdfRule::ChannelNameNotSupported => "HDR001",
+            LdfRule::NodeProtocolVersion => "HDR002",
+            LdfRule::UnknownProtocolVersion => "HDR003",
+        }
+    }
+
+    /// Severity of the findings of the rule
+    pub fn severity(self) -> LdfSeverity {
+        match self {
+            LdfRule::UnknownNode
+            | LdfRule::UnknownSlave
+            | LdfRule::UnknownSignal
+            | LdfRule::UnknownDiagnosticSignal
+            | LdfRule::UnknownFrame
+            | LdfRule::UnknownScheduleTable
+            | LdfRule::UnknownEncodingType
+            | LdfRule::SignalOutsideFrame
+            | LdfRule::OverlappingSignals
+            | LdfRule::UnalignedByteArray
+            | LdfRule::SignalPublisherMismatch
+            | LdfRule::FrameIdOutOfRange
+            | LdfRule::DuplicateFrameId
+            | LdfRule::ReservedFrameId
+            | LdfRule::DiagnosticFrameId
+            | LdfRule::FrameSize
+            | LdfRule::SlotTooShort
+            | LdfRule::ScalarSignalSize
+            | LdfRule::ArraySignalSize
+            | LdfRule::EncodingValueOutOfRange
+            | LdfRule::OverlappingPhysicalRanges
+            | LdfRule::InvalidNad
+            | LdfRule::DuplicateNad
+            | LdfRule::ReservedSupplierId
+            | LdfRule::ResponseErrorSignal
+            | LdfRule::MissingResponseError
+            | LdfRule::ChannelNameNotSupported => LdfSeverity::Error,
+            LdfRule::DelayNotTimeBaseMultiple | LdfRule::NodeProtocolVersion | LdfRule::UnknownProtocolVersion => {
+                LdfSeverity::Warning
+            }
+        }
+    }
+}
+
+/// Problem found by [`LinLdf::validate`] in a file that could be parsed
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub struct LdfFinding {
+    /// Rule that found the problem
+    pub rule: LdfRule,
+
+    /// Severity of the rule
+    pub severity: LdfSeverity,
+
+    /// Section with the problem, e.g. `Frames`
+    pub section: String,
+
+    /// Description of the problem, naming the elements involved
+    pub message: String,
+
+    /// Location of the problem in the file (e.g. the reference to an unknown signal)
+    pub span: LdfSpan,
+
+    /// Additional information, may span several lines (e.g. a bit map of the frame with the problem)
+    pub note: Option<String>,
+}
+
+impl LdfFinding {
+    pub(crate) fn new(rule: LdfRule, section: &str, span: LdfSpan, message: String) -> LdfFinding {
+        LdfFinding {
+            rule,
+            severity: rule.severity(),
+            section: section.to_string(),
+            message,
+            span,
+            note: None,
+        }
+    }
+
+    pub(crate) fn with_note(mut self, note: String) -> LdfFinding {
+        self.note = Some(note);
+        self
+    }
+
+    /// Finding as a diagnostic that points into `source`, the file that was parsed
+    pub fn diagnostic(&self, source: &str) -> LdfDiagnostic {
+        let snippet = snippet_at(source, self.span.start);
+        // Underline the whole element, up to the end of its first line
+        let length = source
+            .get(self.span.start..self.span.end)
+            .map_or(1, |text| text.lines().next().unwrap_or("").chars().count());
+
+        LdfDiagnostic {
+            severity: self.severity,
+            code: Some(self.rule.id().to_string()),
+            message: self.message.clone(),
+            hint: None,
+            note: self.note.clone(),
+            line: self.span.line,
+            column: self.span.column,
+            length,
+            snippet,
+            context: format!("{} section", self.section),
+        }
+    }
+
+    /// Finding rendered compiler-style without colors, see [`LdfDiagnostic`]
+    pub fn render(&self, source: &str) -> String {
+        self.diagnostic(source).render()
+    }
+
+    /// Finding rendered compiler-style with ANSI colors, see [`LdfDiagnostic`]
+    pub fn render_ansi(&self, source: &str) -> String {
+        self.diagnostic(source).render_ansi()
+    }
+}
+
+impl fmt::Display for LdfFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}[{}] line {}, column {} ({} section): {}",
+            self.severity.label(),
+            self.rule.id(),
+            self.span.line,
+            self.span.column,
+            self.section,
+            self.message
+        )
+    }
+}
+
+/// Result of [`LinLdf::validation_report`]: the rule set the file was validated against, the rules that were applied
+/// and what they found
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub struct LdfValidationReport {
+    /// Version of the specification the file was validated against
+    pub rule_set: LdfRuleSet,
+
+    /// Rules that were applied
+    pub rules: Vec<LdfRule>,
+
+    /// Findings in file order
+    pub findings: Vec<LdfFinding>,
+}
+
+impl LdfValidationReport {
+    /// Number of findings with the given severity
+    pub fn count(&self, severity: LdfSeverity) -> usize {
+        self.findings
+            .iter()
+            .filter(|finding| finding.severity == severity)
+            .count()
+    }
+}
+
+impl fmt::Display for LdfValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let errors = self.count(LdfSeverity::Error);
+        let warnings = self.count(LdfSeverity::Warning);
+        write!(
+            f,
+            "validated against {} ({} rules): {} error{}, {} warning{}",
+            self.rule_set.name(),
+            self.rules.len(),
+            errors,
+            if errors == 1 { "" } else { "s" },
+            warnings,
+            if warnings == 1 { "" } else { "s" }
+        )?;
+        for finding in &self.findings {
+            write!(f, "\n{}", finding)?;
+        }
+        Ok(())
+    }
+}
+
+impl LinLdf {
+    /// Checks the parsed file for problems the grammar can't catch, e.g. a frame that contains a
+    /// signal that is not defined in `Signals`.
+    ///
+    /// Only the rules of the version of the file are applied (see [`LdfRuleSet`]), use
+    /// [`LinLdf::validation_report`] to see which.
+    ///
+    /// Returns the findings in file order (empty if nothing was found). Each finding has a severity
+    /// and the stable identifier of the rule that found it:
+    /// - `REF001` to `REF007`: every name reference points to a defined node, signal, frame,
+    ///   schedule table or encoding type
+    /// - `LAY001` to `LAY004`: the signals of each frame fit in the frame, don't overlap, are byte-aligned if they
+    ///   are byte arrays and are published by the publisher of the frame
+    /// - `ID001` to `ID005`: frame IDs are in range, unique and not reserved, frame sizes are 1 to 8 bytes
+    /// - `SCH001` and `SCH002`: schedule table slots are multiples of the time base and long enough for their frame
+    /// - `ENC001` to `ENC004`: signal sizes are valid and encoding types fit the signals they represent
+    /// - `NODE001` to `NODE004`: NADs are valid and unique, supplier IDs are valid and response error signals are
+    ///   1 bit scalar signals published by their node
+    /// - `NODE005`, `HDR001` and `HDR002`: the file only uses what its version of the specification supports
+    /// - `HDR003`: the protocol version of the file is known
+    pub fn validate(&self) -> Vec<LdfFinding> {
+        self.validation_report().findings
+    }
+
+    /// Validates the file with the rule set of its `LIN_protocol_version`, see [`LinLdf::validate`]
+    pub fn validation_report(&self) -> LdfValidationReport {
+        self.validation_report_for(LdfRuleSet::from_header(&self.header))
+    }
+
+    /// Validates the file with the given rule set, e.g. to check whether a LIN 2.2 file would also be valid
+    /// for LIN 2.1
+    pub fn validation_report_for(&self, rule_set: LdfRuleSet) -> LdfValidationReport {
+        let mut findings = Vec::new();
+        cross_references::check(self, &mut findings);
+        frame_layout::check(self, &mut findings);
+        frame_ids::check(self, &mut findings);
+        schedule_timing::check(self, &mut findings);
+        signal_encodings::check(self, &mut findings);
+        node_attributes::check(self, &mut findings);
+        protocol_versions::check(self, rule_set, &mut findings);
+
+        let rules = rule_set.rules();
+        findings.retain(|finding| rules.contains(&finding.rule));
+        findings.sort_by_key(|finding| finding.span.start);
+
+        LdfValidationReport {
+            rule_set,
+            rules,
+            findings,
+        }
+    }
+}
+
+/// Location of the reference to `name`, or `fallback` (the location of the entry) if it wasn't recorded
+fn reference_span(references: &[LdfNamedSpan], name: &str, fallback: LdfSpan) -> LdfSpan {
+    references
+        .iter()
+        .find(|reference| reference.name == name)
+        .map_or(fallback, |reference| reference.span)
+}
+
+/// Findings of the rules whose identifiers start with `prefix` (e.g. `REF`), so that a test only sees its own rules
+#[cfg(test)]
+fn findings_of(ldf: &LinLdf, prefix: &str) -> Vec<LdfFinding> {
+    ldf.validate()
+        .into_iter()
+        .filter(|finding| finding.rule.id().starts_with(prefix))
+        .collect()
+}
@@ -0,0 +1,180 @@
+use crate::ldf::ldf_signals::{LdfSignal, LdfSignalInitValue};
+use crate::ldf::validation::{reference_span, LdfFinding, LdfRule};
+use crate::ldf::LinLdf;
+use std::collections::HashMap;
+
+/// `NODE001` to `NODE004`: NADs are valid and unique, supplier IDs don't set the reserved bit and the response error
+/// signal of a node is a 1 bit scalar signal published by the node
+pub(crate) fn check(ldf: &LinLdf, findings: &mut Vec<LdfFinding>) {
+    let signals: HashMap<&str, &LdfSignal> = ldf
+        .signals
+        .iter()
+        .map(|signal| (signal.name.as_str(), signal))
+        .collect();
+
+    let mut first_with_nad: HashMap<u8, &str> = HashMap::new();
+    for node in &ldf.node_attributes {
+        let name = node.node_name.as_str();
+
+        let initial_nad = node.initial_nad.filter(|nad| *nad != node.configured_nad);
+        let nads = std::iter::once(("configured", node.configured_nad)).chain(initial_nad.map(|nad| ("initial", nad)));
+        for (kind, nad) in nads {
+            let reserved = match nad {
+                0x00 => "reserved for go to sleep commands",
+                0x7E => "the functional NAD",
+                0x7F => "the broadcast NAD",
+                _ => continue,
+            };
+            let message = format!("node `{}` has {} NAD {:#04X}, which is {}", name, kind, nad, reserved);
+            findings.push(LdfFinding::new(
+                LdfRule::InvalidNad,
+                "Node_attributes",
+                node.span,
+                message,
+            ));
+        }
+
+        if let Some(other) = first_with_nad.get(&node.configured_nad) {
+            let message = format!(
+                "node `{}` has configured NAD {:#04X}, which is already used by node `{}`",
+                name, node.configured_nad, other
+            );
+            findings.push(LdfFinding::new(
+                LdfRule::DuplicateNad,
+                "Node_attributes",
+                node.span,
+                message,
+            ));
+        } else {
+            first_with_nad.insert(node.configured_nad, name);
+        }
+
+        if node.supplier_id & 0x8000 != 0 {
+            let message = format!(
+                "node `{}` has supplier ID {:#06X}, but the most significant bit of supplier IDs is reserved",
+                name, node.supplier_id
+            );
+            findings.push(LdfFinding::new(
+                LdfRule::ReservedSupplierId,
+                "Node_attributes",
+                node.span,
+                message,
+            ));
+        }
+
+        // Unknown signals are reported by REF003
+        let response_error = node
+            .response_error
+            .as_ref()
+            .and_then(|response_error| signals.get(response_error.as_str()));
+        if let Some(signal) = response_error {
+            let mut problems = Vec::new();
+            if !matches!(signal.init_value, LdfSignalInitValue::Scalar(_)) {
+                problems.push("a byte array".to_string());
+            } else if signal.signal_size != 1 {
+                problems.push(format!("{} bits long", signal.signal_size));
+            }
+            if signal.published_by != name {
+                problems.push(format!("published by `{}`", signal.published_by));
+            }
+            if !problems.is_empty() {
+                let message = format!(
+                    "response error signal `{}` of node `{}` has to be a 1 bit scalar signal published by the node, but it is {}",
+                    signal.name,
+                    name,
+                    problems.join(" and ")
+                );
+                let span = reference_span(&node.references, &signal.name, node.span);
+                findings.push(LdfFinding::new(
+                    LdfRule::ResponseErrorSignal,
+                    "Node_attributes",
+                    span,
+                    message,
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ldf::validation::findings_of;
+    use crate::ldf::LinLdf;
+
+    #[test]
+    fn test_validate_node_attributes() {
+        let input = r#"
+            LIN_description_file ;
+            LIN_protocol_version = "2.1" ;
+            LIN_language_version = "2.1" ;
+            LIN_speed = 19.2 kbps ;
+
+            Nodes {
+                Master: Master, 5 ms, 0.1 ms ;
+                Slaves: Slave1, Slave2, Slave3 ;
+            }
+
+            Signals {
+                Error1: 1, 0, Slave1, Master ;
+                Error2: 2, 0, Master, Slave2 ;
+            }
+
+            Frames {
+                Frame1: 0x10, Slave1, 1 {
+                    Error1, 0 ;
+                }
+            }
+
+            Node_attributes {
+                Slave1 {
+                    LIN_protocol = "2.1" ;
+                    configured_NAD = 0x01 ;
+                    product_id = 0x1E, 0x1 ;
+                    response_error = Error1 ;
+                }
+                Slave2 {
+                    LIN_protocol = "2.1" ;
+                    configured_NAD = 0x01 ;
+                    initial_NAD = 0x7F ;
+                    product_id = 0x801E, 0x1 ;
+                    response_error = Error2 ;
+                }
+                Slave3 {
+                    LIN_protocol = "2.1" ;
+                    configured_NAD = 0x00 ;
+                    product_id = 0x1E, 0x1 ;
+                }
+            }
+
+            Schedule_tables {
+                Table1 {
+                    Frame1 delay 10 ms ;
+                }
+            }
+        "#;
+
+        let ldf = LinLdf::parse(input).unwrap();
+        let findings = findings_of(&ldf, "NODE");
+        let found: Vec<(&str, &str)> = findings
+            .iter()
+            .map(|finding| (finding.rule.id(), finding.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("NODE001", "node `Slave2` has initial NAD 0x7F, which is the broadcast NAD"),
+                ("NODE002", "node `Slave2` has configured NAD 0x01, which is already used by node `Slave1`"),
+                (
+                    "NODE003",
+                    "node `Slave2` has supplier ID 0x801E, but the most significant bit of supplier IDs is reserved"
+                ),
+                (
+                    "NODE004",
+                    "response error signal `Error2` of node `Slave2` has to be a 1 bit scalar signal published by the node, but it is 2 bits long and published by `Master`"
+                ),
+                ("NODE001", "node `Slave3` has configured NAD 0x00, which is reserved for go to sleep commands"),
+                ("NODE005", "node `Slave3` has no `response_error` signal, which LIN 2.1 requires"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use crate::ldf::validation::rule_sets::parse_version;
+use crate::ldf::validation::{LdfFinding, LdfRule, LdfRuleSet};
+use crate::ldf::LinLdf;
+
+/// `HDR001`, `HDR002`, `HDR003` and `NODE005`: the file only uses what the version of the specification it is
+/// validated against supports, and that version is known
+pub(crate) fn check(ldf: &LinLdf, rule_set: LdfRuleSet, findings: &mut Vec<LdfFinding>) {
+    let version = rule_set.name();
+    let rules = rule_set.rules();
+    let header_span = ldf
+        .section_spans
+        .iter()
+        .find(|section| section.name == "header")
+        .map(|section| section.span)
+        .unwrap_or_default();
+
+    if rules.contains(&LdfRule::UnknownProtocolVersion) && LdfRuleSet::from_known_header(&ldf.header).is_none() {
+        let message = format!(
+            "`LIN_protocol_version` \"{}\" is not a known LIN version, the file is validated against {}",
+            ldf.header.lin_protocol_version, version
+        );
+        findings.push(LdfFinding::new(
+            LdfRule::UnknownProtocolVersion,
+            "header",
+            header_span,
+            message,
+        ));
+    }
+
+    if rules.contains(&LdfRule::ChannelNameNotSupported) && ldf.header.channel_name.is_some() {
+        let message = format!(
+            "`Channel_name` is only supported from LIN 2.2 on, but the file is validated against {}",
+            version
+        );
+        findings.push(LdfFinding::new(
+            LdfRule::ChannelNameNotSupported,
+            "header",
+            header_span,
+            message,
+        ));
+    }
+
+    for node in &ldf.node_attributes {
+        if rules.contains(&LdfRule::MissingResponseError) && node.response_error.is_none() {
+            let message = format!(
+                "node `{}` has no `response_error` signal, which {} requires",
+                node.node_name, version
+            );
+            findings.push(LdfFinding::new(
+                LdfRule::MissingResponseError,
+                "Node_attributes",
+                node.span,
+                message,
+            ));
+        }
+
+        if !rules.contains(&LdfRule::NodeProtocolVersion) {
+            continue;
+        }
+        let node_version = parse_version(&node.lin_protocol);
+        if let (Some(node_version), Some(rule_set_version)) = (node_version, rule_set.lin_version()) {
+            if node_version > rule_set_version {
+                let message = format!(
+                    "node `{}` uses LIN {}, but the file is validated against {}",
+                    node.node_name,
+                    node.lin_protocol.trim(),
+                    version
+                );
+                findings.push(LdfFinding::new(
+                    LdfRule::NodeProtocolVersion,
+                    "Node_attributes",
+                    node.span,
+                    message,
+                ));
+            }
+        }
+    }
+}
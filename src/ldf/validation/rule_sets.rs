@@ -0,0 +1,373 @@
+use crate::ldf::ldf_header::LdfHeader;
+use crate::ldf::validation::LdfRule;
+
+/// Version of the LIN specification a file is validated against, see [`LdfRule`] for the rules.
+///
+/// The rule set is selected from `LIN_protocol_version` of the header (falling back to `LIN_language_version`
+/// for ISO 17987), so that a file is only held to the rules of its own version:
+/// - `Channel_name` is only allowed from LIN 2.2 on
+/// - `Node_attributes` (and with them `response_error`) only exist from LIN 2.0 on
+///
+/// There is no rule for the checksum model (classic up to LIN 1.3, enhanced from LIN 2.0 on): an LDF doesn't
+/// state it, the master picks it for each frame from the `LIN_protocol` of the slave node, so nothing in the file
+/// can contradict it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+pub enum LdfRuleSet {
+    /// LIN 1.3 and older
+    Lin13,
+    /// LIN 2.0
+    Lin20,
+    /// LIN 2.1
+    Lin21,
+    /// LIN 2.2 (and 2.2A), also used for newer LIN 2.x versions
+    Lin22,
+    /// ISO 17987
+    Iso17987,
+    /// SAE J2602
+    J2602,
+}
+
+/// Rules of every version: name references, frame layouts and IDs, schedule timing, signal encodings and a known
+/// protocol version
+const COMMON_RULES: [LdfRule; 23] = [
+    LdfRule::UnknownNode,
+    LdfRule::UnknownSlave,
+    LdfRule::UnknownSignal,
+    LdfRule::UnknownDiagnosticSignal,
+    LdfRule::UnknownFrame,
+    LdfRule::UnknownScheduleTable,
+    LdfRule::UnknownEncodingType,
+    LdfRule::SignalOutsideFrame,
+    LdfRule::OverlappingSignals,
+    LdfRule::UnalignedByteArray,
+    LdfRule::SignalPublisherMismatch,
+    LdfRule::FrameIdOutOfRange,
+    LdfRule::DuplicateFrameId,
+    LdfRule::ReservedFrameId,
+    LdfRule::DiagnosticFrameId,
+    LdfRule::FrameSize,
+    LdfRule::DelayNotTimeBaseMultiple,
+    LdfRule::SlotTooShort,
+    LdfRule::ScalarSignalSize,
+    LdfRule::ArraySignalSize,
+    LdfRule::EncodingValueOutOfRange,
+    LdfRule::OverlappingPhysicalRanges,
+    LdfRule::UnknownProtocolVersion,
+];
+
+/// Rules of the `Node_attributes` section, which was added in LIN 2.0
+const NODE_ATTRIBUTE_RULES: [LdfRule; 4] = [
+    LdfRule::InvalidNad,
+    LdfRule::DuplicateNad,
+    LdfRule::ReservedSupplierId,
+    LdfRule::ResponseErrorSignal,
+];
+
+impl LdfRuleSet {
+    /// Rule set for the version of the file.
+    ///
+    /// Versions that are not recognized (e.g. `3.0` or `abc`) fall back to LIN 2.2, the latest LIN 2.x version,
+    /// and are reported by `HDR003`.
+    pub fn from_header(header: &LdfHeader) -> LdfRuleSet {
+        LdfRuleSet::from_known_header(header).unwrap_or(LdfRuleSet::Lin22)
+    }
+
+    /// Rule set for the version of the file, if it is a known version
+    pub(crate) fn from_known_header(header: &LdfHeader) -> Option<LdfRuleSet> {
+        if header.is_j2602() {
+            return Some(LdfRuleSet::J2602);
+        }
+        if header.is_iso17987() {
+            return Some(LdfRuleSet::Iso17987);
+        }
+        match parse_version(&header.lin_protocol_version)? {
+            (major, _) if major < 2 => Some(LdfRuleSet::Lin13),
+            (2, 0) => Some(LdfRuleSet::Lin20),
+            (2, 1) => Some(LdfRuleSet::Lin21),
+            (2, _) => Some(LdfRuleSet::Lin22),
+            _ => None,
+        }
+    }
+
+    /// Major and minor number of the LIN version, e.g. `(2, 1)` for LIN 2.1 (none for ISO 17987 and SAE J2602)
+    pub(crate) fn lin_version(self) -> Option<(u32, u32)> {
+        match self {
+            LdfRuleSet::Lin13 => Some((1, 3)),
+            LdfRuleSet::Lin20 => Some((2, 0)),
+            LdfRuleSet::Lin21 => Some((2, 1)),
+            LdfRuleSet::Lin22 => Some((2, 2)),
+            LdfRuleSet::Iso17987 | LdfRuleSet::J2602 => None,
+        }
+    }
+
+    /// Name of the specification, e.g. `LIN 2.1`
+    pub fn name(self) -> &'static str {
+        match self {
+            LdfRuleSet::Lin13 => "LIN 1.3",
+            LdfRuleSet::Lin20 => "LIN 2.0",
+            LdfRuleSet::Lin21 => "LIN 2.1",
+            LdfRuleSet::Lin22 => "LIN 2.2",
+            LdfRuleSet::Iso17987 => "ISO 17987",
+            LdfRuleSet::J2602 => "SAE J2602",
+        }
+    }
+
+    /// Rules that apply to files of this version
+    pub fn rules(self) -> Vec<LdfRule> {
+        let mut rules = COMMON_RULES.to_vec();
+        match self {
+            LdfRuleSet::Lin13 => {
+                rules.push(LdfRule::ChannelNameNotSupported);
+            }
+            LdfRuleSet::Lin20 | LdfRuleSet::Lin21 => {
+                rules.extend(NODE_ATTRIBUTE_RULES);
+                rules.extend([
+                    LdfRule::MissingResponseError,
+                    LdfRule::ChannelNameNotSupported,
+                    LdfRule::NodeProtocolVersion,
+                ]);
+            }
+            LdfRuleSet::Lin22 => {
+                rules.extend(NODE_ATTRIBUTE_RULES);
+                rules.extend([LdfRule::MissingResponseError, LdfRule::NodeProtocolVersion]);
+            }
+            LdfRuleSet::Iso17987 => {
+                rules.extend(NODE_ATTRIBUTE_RULES);
+                rules.push(LdfRule::MissingResponseError);
+            }
+            LdfRuleSet::J2602 => {
+                rules.extend(NODE_ATTRIBUTE_RULES);
+                rules.push(LdfRule::ChannelNameNotSupported);
+            }
+        }
+        rules
+    }
+}
+
+/// Major and minor number of a version like `2.1`, ignoring a revision letter (e.g. `2.2A`)
+pub(crate) fn parse_version(version: &str) -> Option<(u32, u32)> {
+    let version = version.trim();
+    let version = version
+        .strip_suffix(|c: char| c.is_ascii_alphabetic())
+        .unwrap_or(version);
+    let (major, minor) = version.split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ldf::ldf_diagnostic::LdfSeverity;
+    use crate::ldf::validation::protocol_versions;
+    use crate::ldf::LinLdf;
+
+    fn header(lin_protocol_version: &str) -> LdfHeader {
+        LdfHeader {
+            lin_protocol_version: lin_protocol_version.to_string(),
+            lin_language_version: lin_protocol_version.to_string(),
+            lin_speed: 19200,
+            channel_name: None,
+        }
+    }
+
+    #[test]
+    fn test_rule_set_from_header() {
+        assert_eq!(LdfRuleSet::from_header(&header("1.3")), LdfRuleSet::Lin13);
+        assert_eq!(LdfRuleSet::from_header(&header("2.0")), LdfRuleSet::Lin20);
+        assert_eq!(LdfRuleSet::from_header(&header("2.1")), LdfRuleSet::Lin21);
+        assert_eq!(LdfRuleSet::from_header(&header("2.2")), LdfRuleSet::Lin22);
+        assert_eq!(LdfRuleSet::from_known_header(&header("2.2A")), Some(LdfRuleSet::Lin22));
+        assert_eq!(parse_version("2.2A"), Some((2, 2)));
+        assert_eq!(LdfRuleSet::from_header(&header("ISO17987:2015")), LdfRuleSet::Iso17987);
+        assert_eq!(LdfRuleSet::from_header(&header("J2602_1_1.0")), LdfRuleSet::J2602);
+
+        assert!(LdfRuleSet::Lin21.rules().contains(&LdfRule::ChannelNameNotSupported));
+        assert!(!LdfRuleSet::Lin22.rules().contains(&LdfRule::ChannelNameNotSupported));
+        assert!(!LdfRuleSet::Lin13.rules().contains(&LdfRule::MissingResponseError));
+    }
+
+    #[test]
+    fn test_validate_protocol_versions() {
+        let ldf = |version: &str| {
+            format!(
+                r#"
+                LIN_description_file ;
+                LIN_protocol_version = "{version}" ;
+                LIN_language_version = "{version}" ;
+                LIN_speed = 19.2 kbps ;
+                Channel_name = "DB" ;
+
+                Nodes {{
+                    Master: Master, 5 ms, 0.1 ms ;
+                    Slaves: Slave1 ;
+                }}
+
+                Signals {{
+                    Signal1: 8, 0, Master, Slave1 ;
+                }}
+
+                Frames {{
+                    Frame1: 0x10, Master, 1 {{
+                        Signal1, 0 ;
+                    }}
+                }}
+
+                Node_attributes {{
+                    Slave1 {{
+                        LIN_protocol = "2.2" ;
+                        configured_NAD = 0x01 ;
+                        product_id = 0x1E, 0x1 ;
+                    }}
+                }}
+
+                Schedule_tables {{
+                    Table1 {{
+                        Frame1 delay 10 ms ;
+                    }}
+                }}
+                "#
+            )
+        };
+
+        // A 2.0 file can't have a channel name or a 2.2 node and needs response error signals
+        let lin20 = LinLdf::parse(&ldf("2.0")).unwrap();
+        let report = lin20.validation_report();
+        assert_eq!(report.rule_set, LdfRuleSet::Lin20);
+        assert_eq!(report.rules, LdfRuleSet::Lin20.rules());
+        let found: Vec<(&str, &str)> = report
+            .findings
+            .iter()
+            .map(|finding| (finding.rule.id(), finding.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    "HDR001",
+                    "`Channel_name` is only supported from LIN 2.2 on, but the file is validated against LIN 2.0"
+                ),
+                (
+                    "NODE005",
+                    "node `Slave1` has no `response_error` signal, which LIN 2.0 requires"
+                ),
+                (
+                    "HDR002",
+                    "node `Slave1` uses LIN 2.2, but the file is validated against LIN 2.0"
+                ),
+            ]
+        );
+
+        // The same file is fine for LIN 2.2, apart from the response error signal
+        let lin22 = LinLdf::parse(&ldf("2.2")).unwrap();
+        let report = lin22.validation_report();
+        assert_eq!(report.rule_set, LdfRuleSet::Lin22);
+        let rules: Vec<&str> = report.findings.iter().map(|finding| finding.rule.id()).collect();
+        assert_eq!(rules, vec!["NODE005"]);
+        assert_eq!(
+            report.to_string().lines().next(),
+            Some("validated against LIN 2.2 (29 rules): 1 error, 0 warnings")
+        );
+
+        // Rules outside of the rule set don't produce findings in the first place
+        let mut findings = Vec::new();
+        protocol_versions::check(&lin22, LdfRuleSet::Lin22, &mut findings);
+        let rules: Vec<&str> = findings.iter().map(|finding| finding.rule.id()).collect();
+        assert_eq!(rules, vec!["NODE005"]);
+
+        // A rule set can also be chosen explicitly, the version checks then use its version instead of the file's
+        let report = lin22.validation_report_for(LdfRuleSet::Lin21);
+        let found: Vec<(&str, &str)> = report
+            .findings
+            .iter()
+            .map(|finding| (finding.rule.id(), finding.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    "HDR001",
+                    "`Channel_name` is only supported from LIN 2.2 on, but the file is validated against LIN 2.1"
+                ),
+                (
+                    "NODE005",
+                    "node `Slave1` has no `response_error` signal, which LIN 2.1 requires"
+                ),
+                (
+                    "HDR002",
+                    "node `Slave1` uses LIN 2.2, but the file is validated against LIN 2.1"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_unknown_protocol_version() {
+        // Unknown versions fall back to LIN 2.2
+        for version in ["abc", "3.0", ""] {
+            assert_eq!(LdfRuleSet::from_known_header(&header(version)), None);
+            assert_eq!(LdfRuleSet::from_header(&header(version)), LdfRuleSet::Lin22);
+        }
+        assert_eq!(LdfRuleSet::from_header(&header("2.3")), LdfRuleSet::Lin22);
+
+        // and are reported
+        let input = r#"
+            LIN_description_file ;
+            LIN_protocol_version = "3.0" ;
+            LIN_language_version = "3.0" ;
+            LIN_speed = 19.2 kbps ;
+
+            Nodes {
+                Master: Master, 5 ms, 0.1 ms ;
+                Slaves: Slave1 ;
+            }
+
+            Signals {
+                Signal1: 8, 0, Master, Slave1 ;
+                Error1: 1, 0, Slave1, Master ;
+            }
+
+            Frames {
+                Frame1: 0x10, Master, 1 {
+                    Signal1, 0 ;
+                }
+                Frame2: 0x11, Slave1, 1 {
+                    Error1, 0 ;
+                }
+            }
+
+            Node_attributes {
+                Slave1 {
+                    LIN_protocol = "2.1" ;
+                    configured_NAD = 0x01 ;
+                    product_id = 0x1E, 0x1 ;
+                    response_error = Error1 ;
+                }
+            }
+
+            Schedule_tables {
+                Table1 {
+                    Frame1 delay 10 ms ;
+                    Frame2 delay 10 ms ;
+                }
+            }
+        "#;
+        let report = LinLdf::parse(input).unwrap().validation_report();
+        assert_eq!(report.rule_set, LdfRuleSet::Lin22);
+        let found: Vec<(&str, &str)> = report
+            .findings
+            .iter()
+            .map(|finding| (finding.rule.id(), finding.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![(
+                "HDR003",
+                "`LIN_protocol_version` \"3.0\" is not a known LIN version, the file is validated against LIN 2.2"
+            )]
+        );
+        assert_eq!(report.findings[0].severity, LdfSeverity::Warning);
+    }
+}
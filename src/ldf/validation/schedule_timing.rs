@@ -0,0 +1,246 @@
+use crate::ldf::ldf_schedule_tables::LdfScheduleCommand;
+use crate::ldf::validation::{LdfFinding, LdfRule};
+use crate::ldf::LinLdf;
+use std::collections::HashMap;
+
+/// Nominal frame length in bits without the data: break, sync and protected identifier (34 bits) and the checksum byte
+const FRAME_OVERHEAD_BITS: f32 = 34.0 + 10.0;
+
+/// TFrame_Maximum is 40% longer than the nominal frame length, leaving room for inter-byte spaces and the response space
+const FRAME_TOLERANCE: f32 = 1.4;
+
+/// Diagnostic frames are 8 bytes long. All schedule commands other than `Frame` (MasterReq, SlaveResp and the node
+/// configuration commands such as AssignNAD) are sent in a master request or slave response frame, so they take as
+/// long as a diagnostic frame.
+const DIAGNOSTIC_FRAME_SIZE: u8 = 8;
+
+/// `SCH001` and `SCH002`: each slot of a schedule table is a multiple of the master time base and long enough for
+/// its frame (TFrame_Maximum)
+pub(crate) fn check(ldf: &LinLdf, findings: &mut Vec<LdfFinding>) {
+    let time_base = ldf.nodes.master.time_base_ms;
+    let time_base_us = microseconds(time_base);
+    let lin_speed = ldf.header.lin_speed;
+
+    let frame_sizes = frame_sizes(ldf);
+
+    for table in &ldf.schedule_tables {
+        for frame_delay in &table.frame_delays {
+            let delay = frame_delay.frame_time;
+            let delay_us = microseconds(delay);
+            let (slot, frame_size) = match &frame_delay.command {
+                LdfScheduleCommand::Frame { frame_name } => (
+                    format!("frame `{}`", frame_name),
+                    frame_sizes.get(frame_name.as_str()).copied(),
+                ),
+                // Sent in a diagnostic frame, see DIAGNOSTIC_FRAME_SIZE
+                command => (
+                    format!("`{}` command", command_name(command)),
+                    Some(DIAGNOSTIC_FRAME_SIZE),
+                ),
+            };
+
+            if time_base_us > 0 && !delay_us.is_multiple_of(time_base_us) {
+                let message = format!(
+                    "slot of {} in schedule table `{}` is {} ms, which is not a multiple of the time base ({} ms)",
+                    slot, table.schedule_table_name, delay, time_base
+                );
+                findings.push(LdfFinding::new(
+                    LdfRule::DelayNotTimeBaseMultiple,
+                    "Schedule_tables",
+                    frame_delay.span,
+                    message,
+                ));
+            }
+
+            // Unknown frames are reported by REF005
+            let frame_size = match frame_size {
+                Some(frame_size) if lin_speed > 0 => frame_size,
+                _ => continue,
+            };
+            let minimum = frame_maximum(frame_size, lin_speed);
+            let minimum_us = microseconds(minimum);
+            if delay_us < minimum_us {
+                let mut message = format!(
+                    "slot of {} in schedule table `{}` is {} ms, but a frame with {} data bytes needs at least {:.2} ms at {} bit/s",
+                    slot, table.schedule_table_name, delay, frame_size, minimum, lin_speed
+                );
+                if time_base_us > 0 {
+                    let slot_minimum = minimum_us.div_ceil(time_base_us) * time_base_us;
+                    let slot_minimum = slot_minimum as f32 / 1000.0;
+                    message.push_str(&format!(" ({} ms with a time base of {} ms)", slot_minimum, time_base));
+                }
+                findings.push(LdfFinding::new(
+                    LdfRule::SlotTooShort,
+                    "Schedule_tables",
+                    frame_delay.span,
+                    message,
+                ));
+            }
+        }
+    }
+}
+
+/// TFrame_Maximum in milliseconds of a frame with `frame_size` data bytes at `lin_speed` bits per second
+fn frame_maximum(frame_size: u8, lin_speed: u16) -> f32 {
+    let nominal_bits = FRAME_OVERHEAD_BITS + 10.0 * f32::from(frame_size);
+    FRAME_TOLERANCE * nominal_bits * 1000.0 / f32::from(lin_speed)
+}
+
+/// Time in whole microseconds. Times are compared in microseconds, which is finer than any time written in an LDF,
+/// so float rounding errors don't matter.
+fn microseconds(milliseconds: f32) -> u64 {
+    (f64::from(milliseconds) * 1000.0).round() as u64
+}
+
+/// Data bytes of each frame that can be scheduled. Event triggered and sporadic frames carry one of their associated
+/// frames, so they take as long as the longest of them.
+fn frame_sizes(ldf: &LinLdf) -> HashMap<&str, u8> {
+    let mut sizes: HashMap<&str, u8> = ldf
+        .frames
+        .iter()
+        .map(|frame| (frame.frame_name.as_str(), frame.frame_size))
+        .collect();
+    let longest = |associated_frames: &[String], sizes: &HashMap<&str, u8>| {
+        associated_frames
+            .iter()
+            .filter_map(|frame| sizes.get(frame.as_str()).copied())
+            .max()
+    };
+
+    for frame in &ldf.event_triggered_frames {
+        if let Some(size) = longest(&frame.associated_frames, &sizes) {
+            sizes.insert(frame.frame_name.as_str(), size);
+        }
+    }
+    for frame in &ldf.sporadic_frames {
+        if let Some(size) = longest(&frame.associated_frames, &sizes) {
+            sizes.insert(frame.name.as_str(), size);
+        }
+    }
+    for frame in &ldf.diagnostic_frames {
+        sizes.insert(frame.frame_name.as_str(), DIAGNOSTIC_FRAME_SIZE);
+    }
+    sizes
+}
+
+/// Keyword of a schedule table command, as written in the file
+fn command_name(command: &LdfScheduleCommand) -> &'static str {
+    match command {
+        LdfScheduleCommand::Frame { .. } => "Frame",
+        LdfScheduleCommand::MasterReq => "MasterReq",
+        LdfScheduleCommand::SlaveResp => "SlaveResp",
+        LdfScheduleCommand::AssignNad { .. } => "AssignNAD",
+        LdfScheduleCommand::ConditionalChangeNad { .. } => "ConditionalChangeNAD",
+        LdfScheduleCommand::DataDump { .. } => "DataDump",
+        LdfScheduleCommand::SaveConfiguration { .. } => "SaveConfiguration",
+        LdfScheduleCommand::AssignFrameIdRange { .. } => "AssignFrameIdRange",
+        LdfScheduleCommand::FreeFormat { .. } => "FreeFormat",
+        LdfScheduleCommand::AssignFrameId { .. } => "AssignFrameId",
+        LdfScheduleCommand::UnassignFrameId { .. } => "UnassignFrameId",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::frame_maximum;
+    use crate::ldf::validation::findings_of;
+    use crate::ldf::LinLdf;
+
+    #[test]
+    fn test_frame_maximum() {
+        // 1.4 * (34 + 10 * (8 + 1)) bits at 19200 bit/s
+        assert!((frame_maximum(8, 19200) - 9.041_667).abs() < 1e-4);
+        assert!((frame_maximum(1, 10417) - 7.257_368).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_validate_schedule_timing() {
+        let input = r#"
+            LIN_description_file ;
+            LIN_protocol_version = "2.1" ;
+            LIN_language_version = "2.1" ;
+            LIN_speed = 19.2 kbps ;
+
+            Nodes {
+                Master: Master, 5 ms, 0.1 ms ;
+                Slaves: Slave1 ;
+            }
+
+            Signals {
+                Signal1: 8, 0, Master, Slave1 ;
+                Signal2: 8, 0, Slave1, Master ;
+            }
+
+            Frames {
+                Frame1: 0x10, Master, 1 {
+                    Signal1, 0 ;
+                }
+                Frame2: 0x11, Slave1, 8 {
+                    Signal2, 0 ;
+                }
+            }
+
+            Event_triggered_frames {
+                ETF1: 0x3A, Frame2 ;
+            }
+
+            Node_attributes {
+                Slave1 {
+                    LIN_protocol = "2.1" ;
+                    configured_NAD = 0x01 ;
+                    product_id = 0x1E, 0x1 ;
+                }
+            }
+
+            Schedule_tables {
+                Table1 {
+                    Frame1 delay 5 ms ;
+                    Frame1 delay 7.5 ms ;
+                    Frame2 delay 5 ms ;
+                    ETF1 delay 10 ms ;
+                    AssignNAD { Slave1 } delay 5 ms ;
+                }
+            }
+        "#;
+
+        let ldf = LinLdf::parse(input).unwrap();
+        let findings = findings_of(&ldf, "SCH");
+        let found: Vec<(&str, &str)> = findings
+            .iter()
+            .map(|finding| (finding.rule.id(), finding.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    "SCH001",
+                    "slot of frame `Frame1` in schedule table `Table1` is 7.5 ms, which is not a multiple of the time base (5 ms)"
+                ),
+                (
+                    "SCH002",
+                    "slot of frame `Frame2` in schedule table `Table1` is 5 ms, but a frame with 8 data bytes needs at least 9.04 ms at 19200 bit/s (10 ms with a time base of 5 ms)"
+                ),
+                (
+                    "SCH002",
+                    "slot of `AssignNAD` command in schedule table `Table1` is 5 ms, but a frame with 8 data bytes needs at least 9.04 ms at 19200 bit/s (10 ms with a time base of 5 ms)"
+                ),
+            ]
+        );
+
+        // 7.5 ms is a multiple of a 2.5 ms time base, and 0.9 ms of a 0.3 ms one despite float rounding
+        let input = input.replace("5 ms, 0.1 ms", "2.5 ms, 0.1 ms");
+        let ldf = LinLdf::parse(&input).unwrap();
+        let rules: Vec<&str> = findings_of(&ldf, "SCH")
+            .iter()
+            .map(|finding| finding.rule.id())
+            .collect();
+        assert_eq!(rules, vec!["SCH002", "SCH002"]);
+        let input = input
+            .replace("2.5 ms, 0.1 ms", "0.3 ms, 0.1 ms")
+            .replace("7.5 ms", "0.9 ms");
+        let ldf = LinLdf::parse(&input).unwrap();
+        assert!(!findings_of(&ldf, "SCH001")
+            .iter()
+            .any(|finding| finding.message.contains("0.9 ms")));
+    }
+}
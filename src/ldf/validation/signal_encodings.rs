@@ -0,0 +1,246 @@
+use crate::ldf::ldf_signal_encoding_types::{LdfSignalEncodingType, LdfSignalEncodingTypeValue};
+use crate::ldf::ldf_signals::{LdfSignal, LdfSignalInitValue};
+use crate::ldf::validation::{LdfFinding, LdfRule};
+use crate::ldf::LinLdf;
+use std::collections::HashMap;
+
+/// `ENC001` to `ENC004`: signal sizes are valid for scalar and byte array signals, encoding values fit the raw range of
+/// the signals they represent and the physical ranges of an encoding type don't overlap
+pub(crate) fn check(ldf: &LinLdf, findings: &mut Vec<LdfFinding>) {
+    for signal in &ldf.signals {
+        match signal.init_value {
+            LdfSignalInitValue::Scalar(_) if !(1..=16).contains(&signal.signal_size) => {
+                let message = format!(
+                    "scalar signal `{}` has {} bits, but scalar signals are 1 to 16 bits long",
+                    signal.name, signal.signal_size
+                );
+                findings.push(LdfFinding::new(
+                    LdfRule::ScalarSignalSize,
+                    "Signals",
+                    signal.span,
+                    message,
+                ));
+            }
+            LdfSignalInitValue::Array(_)
+                if signal.signal_size == 0 || signal.signal_size % 8 != 0 || signal.signal_size > 64 =>
+            {
+                let message = format!(
+                    "byte array signal `{}` has {} bits, but byte arrays are 8, 16, 24, 32, 40, 48, 56 or 64 bits long",
+                    signal.name, signal.signal_size
+                );
+                findings.push(LdfFinding::new(
+                    LdfRule::ArraySignalSize,
+                    "Signals",
+                    signal.span,
+                    message,
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    let signals: HashMap<&str, &LdfSignal> = ldf
+        .signals
+        .iter()
+        .map(|signal| (signal.name.as_str(), signal))
+        .collect();
+    let encoding_types: HashMap<&str, &LdfSignalEncodingType> = ldf
+        .signal_encoding_types
+        .iter()
+        .map(|encoding_type| (encoding_type.encoding_type_name.as_str(), encoding_type))
+        .collect();
+
+    // Unknown encoding types and signals are reported by REF007 and REF003
+    for representation in &ldf.signal_representations {
+        let encoding_type = match encoding_types.get(representation.encoding_type_name.as_str()) {
+            Some(encoding_type) => encoding_type,
+            None => continue,
+        };
+        let represented = representation
+            .signal_names
+            .iter()
+            .filter_map(|name| signals.get(name.as_str()));
+        for signal in represented {
+            // Byte arrays have no raw value range
+            if let LdfSignalInitValue::Scalar(_) = signal.init_value {
+                check_raw_range(encoding_type, signal, findings);
+            }
+        }
+    }
+
+    for encoding_type in &ldf.signal_encoding_types {
+        check_physical_ranges(encoding_type, findings);
+    }
+}
+
+/// `ENC003`: the values of `encoding_type` fit the raw range of the scalar `signal`
+fn check_raw_range(encoding_type: &LdfSignalEncodingType, signal: &LdfSignal, findings: &mut Vec<LdfFinding>) {
+    let max_raw = if signal.signal_size >= 63 {
+        i64::MAX
+    } else {
+        (1_i64 << signal.signal_size) - 1
+    };
+
+    for (i, value) in encoding_type.encoding_type_values.iter().enumerate() {
+        let (kind, min, max) = match value {
+            LdfSignalEncodingTypeValue::LogicalValue { value, .. } => {
+                ("logical value", i64::from(*value), i64::from(*value))
+            }
+            LdfSignalEncodingTypeValue::PhysicalValue {
+                min_value, max_value, ..
+            } => ("physical range", i64::from(*min_value), i64::from(*max_value)),
+            LdfSignalEncodingTypeValue::BcdValue | LdfSignalEncodingTypeValue::AsciiValue => continue,
+        };
+        if min < 0 || max > max_raw {
+            let values = if min == max {
+                min.to_string()
+            } else {
+                format!("{} to {}", min, max)
+            };
+            let message = format!(
+                "{} {} of encoding type `{}` doesn't fit the {} bit signal `{}` (raw values 0 to {})",
+                kind, values, encoding_type.encoding_type_name, signal.signal_size, signal.name, max_raw
+            );
+            let span = encoding_type.value_spans.get(i).copied().unwrap_or(encoding_type.span);
+            findings.push(LdfFinding::new(
+                LdfRule::EncodingValueOutOfRange,
+                "Signal_encoding_types",
+                span,
+                message,
+            ));
+        }
+    }
+}
+
+/// `ENC004`: the physical ranges of `encoding_type` don't overlap each other
+fn check_physical_ranges(encoding_type: &LdfSignalEncodingType, findings: &mut Vec<LdfFinding>) {
+    let ranges: Vec<(usize, i32, i32)> = encoding_type
+        .encoding_type_values
+        .iter()
+        .enumerate()
+        .filter_map(|(i, value)| match value {
+            LdfSignalEncodingTypeValue::PhysicalValue {
+                min_value, max_value, ..
+            } => Some((i, *min_value, *max_value)),
+            _ => None,
+        })
+        .collect();
+
+    for (n, (i, min, max)) in ranges.iter().enumerate() {
+        for (_, other_min, other_max) in &ranges[..n] {
+            if min <= other_max && other_min <= max {
+                let message = format!(
+                    "physical range {} to {} of encoding type `{}` overlaps physical range {} to {}",
+                    min, max, encoding_type.encoding_type_name, other_min, other_max
+                );
+                let span = encoding_type.value_spans.get(*i).copied().unwrap_or(encoding_type.span);
+                findings.push(LdfFinding::new(
+                    LdfRule::OverlappingPhysicalRanges,
+                    "Signal_encoding_types",
+                    span,
+                    message,
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ldf::validation::findings_of;
+    use crate::ldf::LinLdf;
+
+    #[test]
+    fn test_validate_signal_encodings() {
+        let input = r#"
+            LIN_description_file ;
+            LIN_protocol_version = "2.1" ;
+            LIN_language_version = "2.1" ;
+            LIN_speed = 19.2 kbps ;
+
+            Nodes {
+                Master: Master, 5 ms, 0.1 ms ;
+                Slaves: Slave1 ;
+            }
+
+            Signals {
+                Signal1: 4, 0, Master, Slave1 ;
+                Signal2: 2, 0, Master, Slave1 ;
+                Signal3: 17, 0, Master, Slave1 ;
+                Signal4: 12, {0, 0}, Master, Slave1 ;
+            }
+
+            Frames {
+                Frame1: 0x10, Master, 8 {
+                    Signal1, 0 ;
+                    Signal2, 4 ;
+                    Signal3, 8 ;
+                    Signal4, 32 ;
+                }
+            }
+
+            Node_attributes {
+                Slave1 {
+                    LIN_protocol = "2.1" ;
+                    configured_NAD = 0x01 ;
+                    product_id = 0x1E, 0x1 ;
+                }
+            }
+
+            Schedule_tables {
+                Table1 {
+                    Frame1 delay 10 ms ;
+                }
+            }
+
+            Signal_encoding_types {
+                EncTemperature {
+                    physical_value, 0, 1023, 0.1, 100, "kelvin" ;
+                }
+                EncMode {
+                    logical_value, 0, "off" ;
+                    logical_value, 7, "boost" ;
+                    physical_value, 1, 3, 1, 0 ;
+                    physical_value, 3, 3, 1, 0 ;
+                }
+            }
+
+            Signal_representation {
+                EncTemperature: Signal1 ;
+                EncMode: Signal2, Signal4 ;
+            }
+        "#;
+
+        let ldf = LinLdf::parse(input).unwrap();
+        let findings = findings_of(&ldf, "ENC");
+        let found: Vec<(&str, &str)> = findings
+            .iter()
+            .map(|finding| (finding.rule.id(), finding.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    "ENC001",
+                    "scalar signal `Signal3` has 17 bits, but scalar signals are 1 to 16 bits long"
+                ),
+                (
+                    "ENC002",
+                    "byte array signal `Signal4` has 12 bits, but byte arrays are 8, 16, 24, 32, 40, 48, 56 or 64 bits long"
+                ),
+                (
+                    "ENC003",
+                    "physical range 0 to 1023 of encoding type `EncTemperature` doesn't fit the 4 bit signal `Signal1` (raw values 0 to 15)"
+                ),
+                (
+                    "ENC003",
+                    "logical value 7 of encoding type `EncMode` doesn't fit the 2 bit signal `Signal2` (raw values 0 to 3)"
+                ),
+                (
+                    "ENC004",
+                    "physical range 3 to 3 of encoding type `EncMode` overlaps physical range 1 to 3"
+                ),
+            ]
+        );
+    }
+}
@@ -41,21 +41,25 @@
 //! - [x] LIN_speed
 //! - [x] (Channel_name)
 //! - [x] Nodes
-//! - [ ] (Node_composition)
+//! - [x] (Node_composition)
 //! - [x] Signals
 //! - [x] (Diagnostic_signals)
 //! - [x] Frames
-//! - [ ] (Sporadic_frame)
-//! - [ ] (Event_triggered_frame)
+//! - [x] (Sporadic_frame)
+//! - [x] (Dynamic_frames) (LIN 1.3 only)
+//! - [x] (Event_triggered_frame)
 //! - [x] (Diagnostic_frames)
 //! - [x] Node_attributes
 //! - [x] Schedule_table
-//! - [ ] (Signal_groups)
+//! - [x] (Signal_groups)
 //! - [x] (Signal_encoding_type)
 //! - [x] (Signal_representation)
 //!
 //! (optional sections are in parentheses)
 //!
+//! Sections may appear in any order after the header. Sections that are not part of the specification
+//! (e.g. vendor specific extensions) are kept as raw text in `unknown_sections`.
+//!
 //! # Example
 //!
 //! ```
@@ -147,10 +151,20 @@
 //! ```
 mod ldf;
 
+pub use ldf::ldf_diagnostic::{LdfDiagnostic, LdfSeverity};
+pub use ldf::ldf_error::LdfError;
+pub use ldf::ldf_schedule_tables::LdfScheduleCommand;
 pub use ldf::ldf_signal_encoding_types::LdfSignalEncodingTypeValue;
 pub use ldf::ldf_signals::LdfSignalInitValue;
+pub use ldf::ldf_span::{LdfNamedSpan, LdfSpan};
+pub use ldf::validation::{LdfFinding, LdfRule, LdfRuleSet, LdfValidationReport};
 pub use ldf::LinLdf;
 
-pub fn parse_ldf(ldf: &str) -> Result<LinLdf, &'static str> {
+pub fn parse_ldf(ldf: &str) -> Result<LinLdf, LdfError> {
     ldf::LinLdf::parse(ldf)
 }
+
+/// Parses the whole file even if it has errors, see [`LinLdf::parse_with_recovery`]
+pub fn parse_ldf_with_recovery(ldf: &str) -> (LinLdf, Vec<LdfError>) {
+    ldf::LinLdf::parse_with_recovery(ldf)
+}
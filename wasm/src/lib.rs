@@ -91,7 +91,8 @@ pub fn parse_ldf_file(ldf_content: &str) -> Result<JsValue, JsValue> {
             Ok(serde_wasm_bindgen::to_value(&ldf)?)
         }
         Err(e) => {
-            let error_msg = format!("Parse error: {}", e);
+            // Compiler-style error with the offending line, e.g. "error: expected `;`\n  --> line 19, ..."
+            let error_msg = e.to_string();
             demo::log_parse_error(&error_msg);
             Err(JsValue::from_str(&error_msg))
         }
@@ -109,7 +110,7 @@ pub fn parse_ldf_to_json(ldf_content: &str) -> Result<String, JsValue> {
             Ok(json) => Ok(json),
             Err(e) => Err(JsValue::from_str(&format!("JSON serialization error: {}", e))),
         },
-        Err(e) => Err(JsValue::from_str(&format!("Parse error: {}", e))),
+        Err(e) => Err(JsValue::from_str(&e.to_string())),
     }
 }
 
@@ -123,7 +124,7 @@ pub fn parse_ldf_to_pretty_json(ldf_content: &str) -> Result<String, JsValue> {
             Ok(json) => Ok(json),
             Err(e) => Err(JsValue::from_str(&format!("JSON serialization error: {}", e))),
         },
-        Err(e) => Err(JsValue::from_str(&format!("Parse error: {}", e))),
+        Err(e) => Err(JsValue::from_str(&e.to_string())),
     }
 }
 
@@ -191,6 +192,6 @@ pub fn get_ldf_stats(ldf_content: &str) -> Result<JsValue, JsValue> {
             });
             Ok(serde_wasm_bindgen::to_value(&stats)?)
         }
-        Err(e) => Err(JsValue::from_str(&format!("Parse error: {}", e))),
+        Err(e) => Err(JsValue::from_str(&e.to_string())),
     }
 }
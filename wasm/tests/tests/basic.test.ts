@@ -105,6 +105,7 @@ describe("lin-ldf WASM package", () => {
         // Verify nodes
         expect(ldf.nodes.master.name).toBe("Master");
         expect(ldf.nodes.master.time_base).toBe("5 ms");
+        expect(ldf.nodes.master.time_base_ms).toBe(5);
         expect(ldf.nodes.master.jitter).toBe("0.1 ms");
         expect(ldf.nodes.slaves).toHaveLength(3);
         expect(ldf.nodes.slaves[0].name).toBe("Slave1");
@@ -187,8 +188,9 @@ describe("lin-ldf WASM package", () => {
         expect(slave1!.function_id).toBe(0x4567);
         expect(slave1!.variant).toBe(8);
         expect(slave1!.response_error).toBe("Signal1");
-        expect(slave1!.configurable_frames).toContain("Frame1");
-        expect(slave1!.configurable_frames).toContain("Frame2");
+        const configurableFrames = slave1!.configurable_frames.map(f => f.frame_name);
+        expect(configurableFrames).toContain("Frame1");
+        expect(configurableFrames).toContain("Frame2");
 
         const slave2 = ldf.node_attributes.find(na => na.node_name === "Slave2");
         expect(slave2).toBeDefined();
@@ -198,6 +200,21 @@ describe("lin-ldf WASM package", () => {
         expect(slave2!.variant).toBe(0x66);
     });
 
+    it("should handle LIN 2.0 message IDs of configurable frames", () => {
+        const lin20Ldf = testLdf
+            .replaceAll('"2.1"', '"2.0"')
+            .replaceAll("Frame1 ;\n           Frame2 ;", "Frame1 = 0x1001 ;\n           Frame2 = 0x1002 ;");
+        const ldf: LinLdf = parse_ldf_file(lin20Ldf);
+
+        expect(ldf.header.lin_protocol_version).toBe("2.0");
+        const slave1 = ldf.node_attributes.find(na => na.node_name === "Slave1");
+        expect(slave1).toBeDefined();
+        expect(slave1!.configurable_frames).toEqual([
+            { frame_name: "Frame1", message_id: 0x1001 },
+            { frame_name: "Frame2", message_id: 0x1002 },
+        ]);
+    });
+
     it("should handle schedule tables correctly", () => {
         const ldf: LinLdf = parse_ldf_file(testLdf);
 
@@ -205,15 +222,39 @@ describe("lin-ldf WASM package", () => {
         expect(ldf.schedule_tables[0].schedule_table_name).toBe("AllFrames");
         expect(ldf.schedule_tables[0].frame_delays).toHaveLength(2);
 
-        const frame1Delay = ldf.schedule_tables[0].frame_delays.find(fd => fd.frame_name === "Frame1");
+        const frameDelay = (frameName: string) =>
+            ldf.schedule_tables[0].frame_delays.find(
+                fd => fd.command.type === "Frame" && fd.command.frame_name === frameName
+            );
+
+        const frame1Delay = frameDelay("Frame1");
         expect(frame1Delay).toBeDefined();
         expect(frame1Delay!.frame_time).toBe(10.0);
 
-        const frame2Delay = ldf.schedule_tables[0].frame_delays.find(fd => fd.frame_name === "Frame2");
+        const frame2Delay = frameDelay("Frame2");
         expect(frame2Delay).toBeDefined();
         expect(frame2Delay!.frame_time).toBe(10.0);
     });
 
+    it("should handle schedule table commands correctly", () => {
+        const ldf: LinLdf = parse_ldf_file(
+            testLdf.replace(
+                "Frame2 delay 10 ms ;",
+                "Frame2 delay 10 ms ;\n        MasterReq delay 20 ms ;\n        AssignNAD { Slave1 } delay 20 ms ;"
+            )
+        );
+
+        const frameDelays = ldf.schedule_tables[0].frame_delays;
+        expect(frameDelays).toHaveLength(4);
+        expect(frameDelays.map(fd => fd.command.type)).toEqual(["Frame", "Frame", "MasterReq", "AssignNad"]);
+
+        expect(frameDelays[2].command).toEqual({ type: "MasterReq" });
+        expect(frameDelays[2].frame_time).toBe(20.0);
+
+        expect(frameDelays[3].command).toEqual({ type: "AssignNad", node_name: "Slave1" });
+        expect(frameDelays[3].frame_time).toBe(20.0);
+    });
+
     it("should handle frame signals correctly", () => {
         const ldf: LinLdf = parse_ldf_file(testLdf);
 